@@ -0,0 +1,1435 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse::ParseStream, parse_macro_input, Attribute, DeriveInput, Fields, LitStr};
+
+#[cfg(feature = "compat")]
+#[proc_macro_derive(AxumErrorResponse, attributes(status_code, code, response))]
+pub fn derive_axum_error_response(input: TokenStream) -> TokenStream {
+    expand(input)
+}
+
+#[cfg(not(feature = "compat"))]
+#[proc_macro_derive(AxumErrorResponse, attributes(response))]
+pub fn derive_axum_error_response(input: TokenStream) -> TokenStream {
+    expand(input)
+}
+
+fn expand(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let type_description = parse_doc(&input.attrs).map(|doc| {
+        quote! { .type_description(#doc.to_string()) }
+    });
+
+    let enum_attrs = match parse_enum_attrs(&input.attrs) {
+        Ok(enum_attrs) => enum_attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let envelope = enum_attrs
+        .envelope
+        .as_ref()
+        .map(|envelope| quote! { .envelope(#envelope) });
+    let encoding = enum_attrs
+        .encoding
+        .as_ref()
+        .map(|encoding| quote! { .encoding(#encoding) });
+    let enum_format = enum_attrs
+        .format
+        .as_ref()
+        .map(|format| quote! { .format(#format) });
+    let enum_content_type = enum_attrs
+        .content_type
+        .as_ref()
+        .map(|content_type| quote! { .content_type(#content_type.to_string()) });
+
+    let variants = if let syn::Data::Enum(data_enum) = input.data {
+        data_enum.variants
+    } else {
+        panic!("AxumErrorResponse can only be derived for enums");
+    };
+
+    let backtrace = cfg!(feature = "backtrace")
+        .then(|| quote! { .backtrace(std::backtrace::Backtrace::capture()) });
+
+    let mut match_arms = Vec::new();
+    let mut nested_arms = Vec::new();
+    let mut log_arms = Vec::new();
+    let mut openapi_docs = Vec::new();
+    let mut catalog_entries = Vec::new();
+
+    for variant in variants.iter() {
+        let ident = &variant.ident;
+
+        let response = match parse_response_attrs(&variant.attrs) {
+            Ok(response) => response,
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        if let Some(log) = &response.log {
+            let (wildcard, _) = match variant_pattern(&name, variant, &[]) {
+                Ok(pattern) => pattern,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            let level = match log {
+                Log::Level(level) => quote! { axum_error_handler::__private::LogLevel::At(#level) },
+                Log::Off => quote! { axum_error_handler::__private::LogLevel::Off },
+            };
+
+            log_arms.push(quote! { #wildcard => #level, });
+        }
+
+        if let Some(nested) = response.nested {
+            // A nested variant's status and code come from the inner error
+            // unless both are overridden.
+            let status_code = response
+                .status_code
+                .clone()
+                .or_else(|| variant.attrs.iter().find_map(parse_status_code));
+            let code = response
+                .code
+                .clone()
+                .or_else(|| variant.attrs.iter().find_map(parse_code_string));
+
+            let catalog_status = match &status_code {
+                Some(status_code) => quote! { Some((#status_code).as_u16()) },
+                None => quote! { None },
+            };
+            let catalog_code = match &code {
+                Some(code) => {
+                    let code = code_string(enum_attrs.namespace.as_ref(), code);
+                    quote! { Some(#code) }
+                }
+                None => quote! { None },
+            };
+            let catalog_internal = response.internal;
+            catalog_entries.push(quote! {
+                axum_error_handler::CatalogEntry {
+                    error: stringify!(#name),
+                    variant: stringify!(#ident),
+                    status: #catalog_status,
+                    code: #catalog_code,
+                    title: None,
+                    internal: #catalog_internal,
+                }
+            });
+
+            if let (Some(status_code), Some(code)) = (status_code, code) {
+                let code = code_string(enum_attrs.namespace.as_ref(), &code);
+                let message = parse_error_message(&variant.attrs)
+                    .map(|message| quote! { .message(#message) });
+                let internal = response.internal.then(|| quote! { .internal() });
+                openapi_docs.push(quote! {
+                    axum_error_handler::ErrorResponseBuilder::new()
+                        .status_code(#status_code)
+                        .code(#code)
+                        #message
+                        #internal
+                        #enum_format
+                        #envelope
+                        #encoding
+                        #enum_content_type
+                        .build()
+                });
+            }
+
+            match nested_arms_for(
+                &name,
+                variant,
+                nested,
+                &response,
+                enum_attrs.namespace.as_ref(),
+            ) {
+                Ok((by_value, by_ref)) => {
+                    nested_arms.push(by_value);
+                    match_arms.push(by_ref);
+                }
+                Err(err) => return err.to_compile_error().into(),
+            }
+            continue;
+        }
+
+        if let Some(key) = response.keys.iter().find(|key| {
+            [
+                "nested_field",
+                "code_prefix",
+                "map",
+                "fallback_status",
+                "fallback_code",
+            ]
+            .iter()
+            .any(|nested_only| key.is_ident(nested_only))
+        }) {
+            return syn::Error::new_spanned(key, "this attribute requires `nested`")
+                .to_compile_error()
+                .into();
+        }
+
+        let mut bindings = Vec::new();
+        let retry_after = match &response.retry_after {
+            Some(RetryAfter::Seconds(secs)) => {
+                Some(quote! { std::time::Duration::from_secs(#secs) })
+            }
+            Some(RetryAfter::Field(field)) => {
+                let binding = syn::Ident::new("__retry_after", proc_macro2::Span::call_site());
+                bindings.push((field.clone(), binding.clone()));
+                Some(quote! {
+                    axum_error_handler::__private::IntoRetryAfter::into_retry_after(
+                        #binding.clone(),
+                    )
+                })
+            }
+            None => None,
+        }
+        .map(|delay| quote! { .retry_after(#delay) });
+
+        let status_code = match &response.status_from {
+            Some(field) => {
+                let binding = syn::Ident::new("__status", proc_macro2::Span::call_site());
+                bindings.push((field.clone(), binding.clone()));
+                quote! {
+                    axum_error_handler::__private::http::StatusCode::try_from(#binding.clone())
+                        .unwrap_or(axum_error_handler::__private::http::StatusCode::INTERNAL_SERVER_ERROR)
+                }
+            }
+            None => response
+                .status_code
+                .clone()
+                .or_else(|| variant.attrs.iter().find_map(parse_status_code))
+                .unwrap_or(quote! { axum_error_handler::__private::http::StatusCode::INTERNAL_SERVER_ERROR }),
+        };
+        let code = match &response.code_from {
+            Some(field) => {
+                let binding = syn::Ident::new("__code", proc_macro2::Span::call_site());
+                bindings.push((field.clone(), binding.clone()));
+                quote! { #binding }
+            }
+            None => response
+                .code
+                .clone()
+                .or_else(|| variant.attrs.iter().find_map(parse_code_string))
+                .unwrap_or_else(|| {
+                    let code = ident.to_string();
+                    quote! { #code }
+                }),
+        };
+        let code_string = code_string(enum_attrs.namespace.as_ref(), &code);
+        let title_text = match &response.title {
+            Some(title) => Some(title.value()),
+            None => parse_doc(&variant.attrs),
+        };
+        let title = title_text
+            .as_ref()
+            .map(|title| quote! { .title(#title.to_string()) });
+        let problem_type = match (&response.problem_type, &enum_attrs.type_base) {
+            (Some(problem_type), _) => Some(quote! { .type_uri(#problem_type.to_string()) }),
+            (None, Some(type_base)) => {
+                Some(quote! { .type_uri(format!("{}{}", #type_base, code)) })
+            }
+            (None, None) => None,
+        };
+        let headers = response.headers.iter().map(|(name, value)| {
+            let name = LitStr::new(&name.value().to_ascii_lowercase(), name.span());
+            quote! {
+                .header(
+                    axum_error_handler::__private::http::HeaderName::from_static(#name),
+                    axum_error_handler::__private::http::HeaderValue::from_static(#value),
+                )
+            }
+        });
+        let message_key = response
+            .message_key
+            .as_ref()
+            .map(|message_key| quote! { .message_key(#message_key) });
+        let instance = response.instance.as_ref().map(|instance| {
+            quote! { .instance(#instance.to_string()) }
+        });
+        let help = response
+            .help
+            .as_ref()
+            .map(|help| quote! { .help_url(#help) });
+        let severity = response
+            .severity
+            .as_ref()
+            .map(|severity| quote! { .severity(#severity) });
+        let internal = response.internal.then(|| quote! { .internal() });
+        let format = response
+            .format
+            .as_ref()
+            .or(enum_attrs.format.as_ref())
+            .map(|format| quote! { .format(#format) });
+        let content_type = response
+            .content_type
+            .as_ref()
+            .or(enum_attrs.content_type.as_ref())
+            .map(|content_type| quote! { .content_type(#content_type.to_string()) });
+
+        let fields = match parse_field_attrs(variant) {
+            Ok(fields) => fields,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let mut extension_members = Vec::new();
+
+        for (index, (field, member)) in fields.extensions.into_iter().enumerate() {
+            let binding = quote::format_ident!("__extension_{}", index);
+            bindings.push((field, binding.clone()));
+            extension_members.push(quote! { .extension_member(#member.to_string(), #binding) });
+        }
+
+        let mut message_args = Vec::new();
+
+        for (index, (field, name)) in fields.message_args.into_iter().enumerate() {
+            let binding = quote::format_ident!("__message_arg_{}", index);
+            bindings.push((field, binding.clone()));
+            message_args.push(quote! { .message_arg(#name, #binding) });
+        }
+
+        let details = fields.body.map(|field| {
+            let binding = syn::Ident::new("__details", proc_macro2::Span::call_site());
+            bindings.push((field, binding.clone()));
+            quote! { .details(#binding) }
+        });
+
+        let (pattern, aliases) = match variant_pattern(&name, variant, &bindings) {
+            Ok(pattern) => pattern,
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        let body = match &variant.fields {
+            Fields::Unit => quote! { format!("{}", error) },
+            Fields::Named(_) => {
+                quote! { error.to_string() }
+            }
+            Fields::Unnamed(_) => {
+                quote! { error.to_string() }
+            }
+        };
+
+        match_arms.push(quote! {
+            #pattern => {
+                #aliases
+                let body = #body;
+                let code = #code_string;
+
+                axum_error_handler::ErrorResponseBuilder::new()
+                    .status_code(#status_code)
+                    #problem_type
+                    .code(code)
+                    .message(body)
+                    #message_key
+                    #(#message_args)*
+                    #title
+                    #type_description
+                    #instance
+                    #help
+                    #severity
+                    #internal
+                    #retry_after
+                    #(#headers)*
+                    #format
+                    #envelope
+                    #encoding
+                    #content_type
+                    #(#extension_members)*
+                    #details
+                    .sources({
+                        #[allow(unused_imports)]
+                        use axum_error_handler::__private::{ErrorSources as _, NoSources as _};
+                        (&axum_error_handler::__private::SourceChain(error)).sources()
+                    })
+                    #backtrace
+                    .build()
+            }
+        });
+
+        let catalog_status = match &response.status_from {
+            Some(_) => quote! { None },
+            None => quote! { Some((#status_code).as_u16()) },
+        };
+        let catalog_code = match &response.code_from {
+            Some(_) => quote! { None },
+            None => quote! { Some(#code_string) },
+        };
+        let catalog_title = match title_text {
+            Some(title) => quote! { Some(#title) },
+            None => quote! { None },
+        };
+        let catalog_internal = response.internal;
+        catalog_entries.push(quote! {
+            axum_error_handler::CatalogEntry {
+                error: stringify!(#name),
+                variant: stringify!(#ident),
+                status: #catalog_status,
+                code: #catalog_code,
+                title: #catalog_title,
+                internal: #catalog_internal,
+            }
+        });
+
+        // Variants whose status or code is read from a field are only known
+        // at runtime, so they are left out of the OpenAPI description.
+        if response.status_from.is_none() && response.code_from.is_none() {
+            let message =
+                parse_error_message(&variant.attrs).map(|message| quote! { .message(#message) });
+            openapi_docs.push(quote! {{
+                let code = #code_string;
+
+                axum_error_handler::ErrorResponseBuilder::new()
+                    .status_code(#status_code)
+                    #problem_type
+                    .code(code)
+                    #message
+                    #title
+                    #instance
+                    #help
+                    #internal
+                    #format
+                    #envelope
+                    #encoding
+                    #content_type
+                    .build()
+            }});
+        }
+    }
+
+    // The renderer taking the error borrows it, so the others consume it.
+    let context = match &enum_attrs.custom_fn {
+        Some(CustomFn::WithError(_)) => quote! {
+            axum_error_handler::ToErrorResponseContext::to_response_context(&self)
+        },
+        _ => quote! {
+            axum_error_handler::IntoErrorResponseContext::into_response_context(self)
+        },
+    };
+    let render = match &enum_attrs.custom_fn {
+        Some(CustomFn::Context(custom_fn)) => quote! {
+            axum_error_handler::__private::RenderedResponse::into_rendered_response(
+                #custom_fn(context),
+            )
+        },
+        Some(CustomFn::WithError(custom_fn)) => quote! {
+            axum_error_handler::__private::RenderedResponse::into_rendered_response(
+                #custom_fn(context, &self),
+            )
+        },
+        Some(CustomFn::WithRequest(custom_fn)) => quote! {
+            let request = axum_error_handler::current_request();
+            axum_error_handler::__private::RenderedResponse::into_rendered_response(
+                #custom_fn(context, request.as_deref()),
+            )
+        },
+        Some(CustomFn::Responder(responder)) => quote! {
+            let request = axum_error_handler::current_request();
+            <#responder as axum_error_handler::CustomErrorResponder>::respond_to(
+                context,
+                request.as_deref(),
+            )
+        },
+        None => quote! { axum::response::IntoResponse::into_response(context) },
+    };
+    // Every conversion is reported to the integrations enabled on the
+    // runtime crate, such as `tracing` and `sentry`.
+    let log_level = quote! {
+        #[allow(unreachable_patterns)]
+        let level = match &self {
+            #(#log_arms)*
+            _ => axum_error_handler::__private::LogLevel::Default,
+        };
+    };
+    let log = quote! {
+        axum_error_handler::__private::report(level, &context);
+    };
+
+    let axum_impl = cfg!(feature = "axum").then(|| {
+        quote! {
+            impl axum::response::IntoResponse for #name {
+                fn into_response(self) -> axum::response::Response {
+                    #log_level
+                    let context = #context;
+                    #log
+                    #render
+                }
+            }
+        }
+    });
+
+    let actix_impl = cfg!(feature = "actix").then(|| {
+        quote! {
+            impl axum_error_handler::__private::actix_web::ResponseError for #name {
+                fn status_code(&self) -> axum_error_handler::__private::actix_web::http::StatusCode {
+                    let context = axum_error_handler::ToErrorResponseContext::to_response_context(self);
+                    axum_error_handler::__private::actix_status(context.status_code())
+                }
+
+                fn error_response(&self) -> axum_error_handler::__private::actix_web::HttpResponse {
+                    #log_level
+                    let context = axum_error_handler::ToErrorResponseContext::to_response_context(self);
+                    #log
+                    context.into_actix_response()
+                }
+            }
+        }
+    });
+
+    let grpc_impl = enum_attrs.grpc.then(|| {
+        quote! {
+            impl From<#name> for axum_error_handler::tonic::Status {
+                fn from(err: #name) -> Self {
+                    axum_error_handler::tonic::Status::from(
+                        axum_error_handler::IntoErrorResponseContext::into_response_context(err),
+                    )
+                }
+            }
+        }
+    });
+
+    // Generate the final impl block. Nested variants are converted by value
+    // when the error is consumed, so the inner error is not borrowed; the
+    // other variants go through the borrowed conversion.
+    let openapi_impl = cfg!(feature = "utoipa").then(|| {
+        quote! {
+            impl axum_error_handler::utoipa::PartialSchema for #name {
+                fn schema() -> axum_error_handler::utoipa::openapi::RefOr<
+                    axum_error_handler::utoipa::openapi::schema::Schema,
+                > {
+                    axum_error_handler::error_schema(&[#(#openapi_docs),*])
+                }
+            }
+
+            impl axum_error_handler::utoipa::ToSchema for #name {
+                fn name() -> std::borrow::Cow<'static, str> {
+                    std::borrow::Cow::Borrowed(stringify!(#name))
+                }
+            }
+
+            impl axum_error_handler::utoipa::IntoResponses for #name {
+                fn responses() -> std::collections::BTreeMap<
+                    String,
+                    axum_error_handler::utoipa::openapi::RefOr<
+                        axum_error_handler::utoipa::openapi::response::Response,
+                    >,
+                > {
+                    axum_error_handler::error_responses(&[#(#openapi_docs),*])
+                }
+            }
+        }
+    });
+
+    let expanded = quote! {
+        impl axum_error_handler::ToErrorResponseContext for #name {
+            fn to_response_context(&self) -> axum_error_handler::ErrorResponseContext {
+                let error = self;
+                match error {
+                    #(#match_arms),*
+                }
+            }
+        }
+
+        impl axum_error_handler::IntoErrorResponseContext for #name {
+            fn into_response_context(self) -> axum_error_handler::ErrorResponseContext {
+                match self {
+                    #(#nested_arms)*
+                    #[allow(unreachable_patterns)]
+                    error => axum_error_handler::ToErrorResponseContext::to_response_context(&error),
+                }
+            }
+        }
+
+        impl axum_error_handler::ErrorCatalog for #name {
+            fn catalog_entries() -> Vec<axum_error_handler::CatalogEntry> {
+                vec![#(#catalog_entries),*]
+            }
+        }
+
+        axum_error_handler::__private::inventory::submit! {
+            axum_error_handler::__private::CatalogRegistration::new(
+                <#name as axum_error_handler::ErrorCatalog>::catalog_entries
+            )
+        }
+
+        #axum_impl
+
+        #actix_impl
+
+        #grpc_impl
+
+        #openapi_impl
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Builds the arms for a `#[response(nested)]` or `#[response(nested_or_map)]`
+/// variant, which takes its context from the error it wraps and
+/// applies the variant's overrides: the `map` function is applied to the inner
+/// context first, then a `status_code` or `code` given on the variant replaces
+/// the inner one, e.g. to keep a downstream service's statuses from leaking.
+///
+/// Returns the arm converting the inner error by value, and the arm borrowing
+/// it for `ToErrorResponseContext`.
+fn nested_arms_for(
+    name: &syn::Ident,
+    variant: &syn::Variant,
+    nested: Nested,
+    response: &ResponseAttrs,
+    namespace: Option<&LitStr>,
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    if let Some(key) = response.keys.iter().find(|key| {
+        ![
+            "nested",
+            "nested_or_map",
+            "nested_field",
+            "log",
+            "code_prefix",
+            "map",
+            "fallback_status",
+            "fallback_code",
+            "status_code",
+            "code",
+            "internal",
+        ]
+        .iter()
+        .any(|ok| key.is_ident(ok))
+    }) {
+        return Err(syn::Error::new_spanned(
+            key,
+            "nested variants take their response from the inner error",
+        ));
+    }
+
+    let field = match (&response.nested_field, &variant.fields) {
+        (Some(field), _) => field.clone(),
+        (None, Fields::Unnamed(fields)) if fields.unnamed.len() == 1 => {
+            FieldRef::Index(0, proc_macro2::Span::call_site())
+        }
+        (None, Fields::Named(fields)) if fields.named.len() == 1 => {
+            FieldRef::Named(fields.named[0].ident.clone().unwrap())
+        }
+        (None, Fields::Unit) => {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "nested variants must wrap an error",
+            ))
+        }
+        (None, _) => {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "select the field holding the nested error, e.g. `nested = \"source\"` or `nested_field = 0`",
+            ))
+        }
+    };
+
+    let optional = is_option(field_type(variant, &field));
+
+    if !optional {
+        if let Some(key) = response
+            .keys
+            .iter()
+            .find(|key| key.is_ident("fallback_status") || key.is_ident("fallback_code"))
+        {
+            return Err(syn::Error::new_spanned(
+                key,
+                "fallbacks are only used when the nested field is an `Option`",
+            ));
+        }
+    }
+
+    let inner = syn::Ident::new("__inner", proc_macro2::Span::call_site());
+    let (pattern, _) = variant_pattern(name, variant, &[(field, inner.clone())])?;
+    let (wildcard, _) = variant_pattern(name, variant, &[])?;
+    let status_code = response
+        .status_code
+        .clone()
+        .or_else(|| variant.attrs.iter().find_map(parse_status_code))
+        .map(|status_code| quote! { .status_code(#status_code) });
+    let code = response
+        .code
+        .clone()
+        .or_else(|| variant.attrs.iter().find_map(parse_code_string));
+    // The variant's own level of the chain, coded like a regular variant.
+    let parent_code = code.clone().unwrap_or_else(|| {
+        let code = variant.ident.to_string();
+        quote! { #code }
+    });
+    let parent_code = code_string(namespace, &parent_code);
+    let code = code.map(|code| {
+        let code = code_string(namespace, &code);
+        quote! { .code(#code) }
+    });
+    let code_prefix = response
+        .code_prefix
+        .as_ref()
+        .map(|prefix| quote! { .code_prefix(#prefix) });
+    let context = match nested {
+        Nested::Context => quote! {
+            axum_error_handler::IntoErrorResponseContext::into_response_context(#inner)
+        },
+        Nested::OrMap => quote! {{
+            #[allow(unused_imports)]
+            use axum_error_handler::__private::{MappedContext as _, NestedContext as _};
+            axum_error_handler::__private::NestedOrMap(#inner).nested_context()
+        }},
+    };
+    let borrowed = quote! {{
+        #[allow(unused_imports)]
+        use axum_error_handler::__private::{
+            BorrowedContext as _, BorrowedMappedContext as _, UnmappedContext as _,
+        };
+        (&&&axum_error_handler::__private::NestedRef(#inner)).nested_context()
+    }};
+
+    // Applies `map` and records the variant in the chain; an empty `Option`
+    // responds with the fallback status and code and the variant's message.
+    let wrap = |context: proc_macro2::TokenStream| {
+        let context = match &response.map {
+            Some(map) => quote! { #map(#context) },
+            None => context,
+        };
+        let nested = quote! {
+            axum_error_handler::__private::nest(
+                #context,
+                #parent_code,
+                message,
+            )
+        };
+
+        if !optional {
+            return nested;
+        }
+
+        let fallback_status = response.fallback_status.clone().unwrap_or(
+            quote! { axum_error_handler::__private::http::StatusCode::INTERNAL_SERVER_ERROR },
+        );
+        let fallback_code = response
+            .fallback_code
+            .as_ref()
+            .map(|code| code_string(namespace, code))
+            .unwrap_or_else(|| parent_code.clone());
+
+        quote! {
+            match #inner {
+                Some(#inner) => #nested,
+                None => axum_error_handler::ErrorResponseBuilder::new()
+                    .status_code(#fallback_status)
+                    .code(#fallback_code)
+                    .message(message)
+                    .build(),
+            }
+        }
+    };
+    let context = wrap(context);
+    let borrowed = wrap(borrowed);
+    let internal = response.internal.then(|| quote! { .internal() });
+    let overrides = quote! {
+        .override_with(|builder| builder #status_code #code #code_prefix #internal)
+    };
+
+    // The message is taken before the match consumes the error.
+    let by_value = quote! {
+        error @ #wildcard => {
+            let message = error.to_string();
+
+            #[allow(unreachable_patterns)]
+            match error {
+                #pattern => (#context) #overrides,
+                _ => unreachable!(),
+            }
+        }
+    };
+    let by_ref = quote! {
+        #pattern => {
+            let message = error.to_string();
+            (#borrowed) #overrides
+        }
+    };
+
+    Ok((by_value, by_ref))
+}
+
+fn field_type<'a>(variant: &'a syn::Variant, field: &FieldRef) -> Option<&'a syn::Type> {
+    variant
+        .fields
+        .iter()
+        .enumerate()
+        .find(|(index, f)| match field {
+            FieldRef::Named(ident) => f.ident.as_ref() == Some(ident),
+            FieldRef::Index(i, _) => i == index,
+        })
+        .map(|(_, f)| &f.ty)
+}
+
+/// Whether `ty` is spelled as an `Option<T>`.
+fn is_option(ty: Option<&syn::Type>) -> bool {
+    match ty {
+        Some(syn::Type::Path(ty)) => ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Collects `///` doc comments into a single line, keeping only the first
+/// paragraph.
+fn parse_doc(attrs: &[Attribute]) -> Option<String> {
+    let mut lines = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("doc")) {
+        if let syn::Meta::NameValue(meta) = &attr.meta {
+            if let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) = &meta.value
+            {
+                let line = lit.value();
+                let line = line.trim();
+
+                if line.is_empty() {
+                    if lines.is_empty() {
+                        continue;
+                    }
+                    break;
+                }
+
+                lines.push(line.to_string());
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
+}
+
+/// Reads the message of a thiserror `#[error("...")]` attribute, with its
+/// `{field}` placeholders left as they are.
+fn parse_error_message(attrs: &[Attribute]) -> Option<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("error"))
+        .find_map(|attr| attr.parse_args::<LitStr>().ok())
+        .map(|message| message.value())
+}
+
+/// Parses `#[status_code(...)]`, which takes either a literal status such as
+/// `"404"`/`404` or any expression evaluating to a `StatusCode`.
+fn parse_status_code(attr: &Attribute) -> Option<proc_macro2::TokenStream> {
+    if attr.path().is_ident("status_code") {
+        let result = attr.parse_args_with(parse_status_value);
+
+        Some(result.unwrap_or_else(|err| err.to_compile_error()))
+    } else {
+        None
+    }
+}
+
+fn parse_status_value(input: ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    if input.peek(LitStr) || input.peek(syn::LitInt) {
+        let (val, span) = if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            (lit.value(), lit.span())
+        } else {
+            let lit: syn::LitInt = input.parse()?;
+            (lit.base10_digits().to_string(), lit.span())
+        };
+
+        match val.parse::<u16>() {
+            Ok(code) if (100..1000).contains(&code) => Ok(
+                quote! { axum_error_handler::__private::http::StatusCode::from_u16(#code).unwrap() },
+            ),
+            _ => Err(syn::Error::new(span, "invalid HTTP status code")),
+        }
+    } else {
+        let expr: syn::Expr = input.parse()?;
+
+        Ok(quote! { #expr })
+    }
+}
+
+/// Parses `#[code(...)]`, which takes either a string literal or any expression
+/// evaluating to a string, such as a shared constant.
+fn parse_code_string(attr: &Attribute) -> Option<proc_macro2::TokenStream> {
+    if attr.path().is_ident("code") {
+        let result = attr.parse_args_with(parse_code_value);
+
+        Some(result.unwrap_or_else(|err| err.to_compile_error()))
+    } else {
+        None
+    }
+}
+
+fn parse_code_value(input: ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    let expr: syn::Expr = input.parse()?;
+
+    Ok(quote! { #expr })
+}
+
+/// Options collected from `#[response(...)]` attributes on the enum itself.
+#[derive(Default)]
+struct EnumAttrs {
+    format: Option<proc_macro2::TokenStream>,
+    envelope: Option<proc_macro2::TokenStream>,
+    encoding: Option<proc_macro2::TokenStream>,
+    content_type: Option<LitStr>,
+    type_base: Option<LitStr>,
+    custom_fn: Option<CustomFn>,
+    grpc: bool,
+    /// Prefix joined to every code with a `.`.
+    namespace: Option<LitStr>,
+}
+
+/// A renderer named by `custom_fn`, `custom_fn_with_error`,
+/// `custom_fn_with_request` or `responder`. The functions may return
+/// `Result<Response, ErrorResponseContext>` instead of a `Response`.
+enum CustomFn {
+    /// `fn(ErrorResponseContext) -> Response`
+    Context(syn::Path),
+    /// `fn(ErrorResponseContext, &YourError) -> Response`
+    WithError(syn::Path),
+    /// `fn(ErrorResponseContext, Option<&RequestInfo>) -> Response`
+    WithRequest(syn::Path),
+    /// A type implementing `CustomErrorResponder`.
+    Responder(syn::Type),
+}
+
+fn parse_enum_attrs(attrs: &[Attribute]) -> syn::Result<EnumAttrs> {
+    let mut enum_attrs = EnumAttrs::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("response")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                enum_attrs.format = Some(parse_format(&meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("envelope") {
+                enum_attrs.envelope = Some(parse_envelope(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("encoding") {
+                enum_attrs.encoding = Some(parse_encoding(&meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("content_type") {
+                enum_attrs.content_type = Some(parse_content_type(meta.value()?)?);
+                Ok(())
+            } else if meta.path.is_ident("type_base") {
+                enum_attrs.type_base = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("namespace") {
+                enum_attrs.namespace = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("grpc") {
+                if !cfg!(feature = "tonic") {
+                    return Err(meta.error("`grpc` requires the `tonic` feature"));
+                }
+
+                enum_attrs.grpc = true;
+                Ok(())
+            } else if meta.path.is_ident("custom_fn")
+                || meta.path.is_ident("custom_fn_with_error")
+                || meta.path.is_ident("custom_fn_with_request")
+                || meta.path.is_ident("responder")
+            {
+                if enum_attrs.custom_fn.is_some() {
+                    return Err(meta.error("only one custom renderer can be set"));
+                }
+
+                enum_attrs.custom_fn = Some(if meta.path.is_ident("responder") {
+                    CustomFn::Responder(meta.value()?.parse()?)
+                } else if meta.path.is_ident("custom_fn") {
+                    CustomFn::Context(parse_path(meta.value()?)?)
+                } else if meta.path.is_ident("custom_fn_with_request") {
+                    CustomFn::WithRequest(parse_path(meta.value()?)?)
+                } else {
+                    CustomFn::WithError(parse_path(meta.value()?)?)
+                });
+                Ok(())
+            } else {
+                Err(meta.error("unsupported response attribute"))
+            }
+        })?;
+    }
+
+    Ok(enum_attrs)
+}
+
+/// The expression building a variant's code as a `String`, joined to the
+/// enum's `namespace` when it has one.
+fn code_string(
+    namespace: Option<&LitStr>,
+    code: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match namespace {
+        Some(namespace) => quote! { format!("{}.{}", #namespace, #code) },
+        None => quote! { (#code).to_string() },
+    }
+}
+
+fn parse_format(format: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    match format.value().as_str() {
+        "envelope" => Ok(quote! { axum_error_handler::ErrorFormat::Envelope }),
+        "problem" => Ok(quote! { axum_error_handler::ErrorFormat::Problem }),
+        "jsonapi" => Ok(quote! { axum_error_handler::ErrorFormat::JsonApi }),
+        "graphql" => Ok(quote! { axum_error_handler::ErrorFormat::GraphQl }),
+        "text" => Ok(quote! { axum_error_handler::ErrorFormat::Text }),
+        "html" => Ok(quote! { axum_error_handler::ErrorFormat::Html }),
+        _ => Err(syn::Error::new(
+            format.span(),
+            "unknown format, expected one of \"envelope\", \"problem\", \"jsonapi\", \"graphql\", \"text\" or \"html\"",
+        )),
+    }
+}
+
+/// Parses `envelope(result = "...", error = "...", code = "...", message = "...")`
+/// and the `omit_result`/`flat` flags into an `EnvelopeConfig` expression.
+fn parse_envelope(meta: &syn::meta::ParseNestedMeta) -> syn::Result<proc_macro2::TokenStream> {
+    let mut setters = Vec::new();
+
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("omit_result") {
+            setters.push(quote! { .omit_result() });
+            return Ok(());
+        } else if meta.path.is_ident("flat") {
+            setters.push(quote! { .flat() });
+            return Ok(());
+        }
+
+        let setter = ["result", "error", "code", "message"]
+            .iter()
+            .find(|key| meta.path.is_ident(key))
+            .map(|key| quote::format_ident!("{}_member", key))
+            .ok_or_else(|| meta.error("unsupported envelope key"))?;
+        let value: LitStr = meta.value()?.parse()?;
+
+        setters.push(quote! { .#setter(#value) });
+        Ok(())
+    })?;
+
+    Ok(quote! { axum_error_handler::EnvelopeConfig::new() #(#setters)* })
+}
+
+/// Maps an `encoding = "..."` value to its `BodyEncoding` variant. Variants
+/// behind a cargo feature fail to resolve unless that feature is enabled.
+fn parse_encoding(encoding: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    match encoding.value().as_str() {
+        "json" => Ok(quote! { axum_error_handler::BodyEncoding::Json }),
+        "xml" => Ok(quote! { axum_error_handler::BodyEncoding::Xml }),
+        "msgpack" => Ok(quote! { axum_error_handler::BodyEncoding::MsgPack }),
+        "cbor" => Ok(quote! { axum_error_handler::BodyEncoding::Cbor }),
+        _ => Err(syn::Error::new(
+            encoding.span(),
+            "unknown encoding, expected \"json\", \"xml\", \"msgpack\" or \"cbor\"",
+        )),
+    }
+}
+
+fn parse_severity(severity: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    match severity.value().as_str() {
+        "info" => Ok(quote! { axum_error_handler::Severity::Info }),
+        "warn" => Ok(quote! { axum_error_handler::Severity::Warn }),
+        "error" => Ok(quote! { axum_error_handler::Severity::Error }),
+        _ => Err(syn::Error::new(
+            severity.span(),
+            "unknown severity, expected \"info\", \"warn\" or \"error\"",
+        )),
+    }
+}
+
+/// Parses a function path, written either bare (`custom_fn = errors::render`)
+/// or as a string (`custom_fn = "crate::errors::render"`).
+fn parse_path(input: ParseStream) -> syn::Result<syn::Path> {
+    if input.peek(LitStr) {
+        input.parse::<LitStr>()?.parse()
+    } else {
+        input.parse()
+    }
+}
+
+fn parse_content_type(input: ParseStream) -> syn::Result<LitStr> {
+    let content_type: LitStr = input.parse()?;
+    let name = LitStr::new("content-type", content_type.span());
+    validate_header(&name, &content_type)?;
+
+    Ok(content_type)
+}
+
+/// Options collected from every `#[response(...)]` attribute on a variant.
+#[derive(Default)]
+struct ResponseAttrs {
+    status_code: Option<proc_macro2::TokenStream>,
+    code: Option<proc_macro2::TokenStream>,
+    problem_type: Option<LitStr>,
+    title: Option<LitStr>,
+    message_key: Option<LitStr>,
+    instance: Option<LitStr>,
+    help: Option<LitStr>,
+    content_type: Option<LitStr>,
+    format: Option<proc_macro2::TokenStream>,
+    severity: Option<proc_macro2::TokenStream>,
+    headers: Vec<(LitStr, LitStr)>,
+    retry_after: Option<RetryAfter>,
+    status_from: Option<FieldRef>,
+    code_from: Option<FieldRef>,
+    nested: Option<Nested>,
+    /// Field holding the nested error, for variants with several fields.
+    nested_field: Option<FieldRef>,
+    code_prefix: Option<LitStr>,
+    /// `fn(ErrorResponseContext) -> ErrorResponseContext` applied to the
+    /// nested error's context.
+    map: Option<syn::Path>,
+    /// Status and code used when an `Option` nested field is `None`.
+    fallback_status: Option<proc_macro2::TokenStream>,
+    fallback_code: Option<proc_macro2::TokenStream>,
+    log: Option<Log>,
+    /// Bodies show a generic code and message instead of the variant's.
+    internal: bool,
+    /// Every key given, to reject the ones a nested variant cannot use.
+    keys: Vec<syn::Path>,
+}
+
+/// Level of the event emitted when a variant is converted into a response.
+enum Log {
+    Level(proc_macro2::TokenStream),
+    Off,
+}
+
+/// How a nested variant converts the error it wraps.
+#[derive(Clone, Copy)]
+enum Nested {
+    /// `nested`: through `IntoErrorResponseContext`.
+    Context,
+    /// `nested_or_map`: through `IntoErrorResponseContext` when implemented,
+    /// and the runtime mapper registered for the type otherwise.
+    OrMap,
+}
+
+enum RetryAfter {
+    Seconds(syn::LitInt),
+    Field(FieldRef),
+}
+
+/// Options collected from `#[response(...)]` attributes on a variant's fields.
+#[derive(Default)]
+struct FieldAttrs {
+    /// Fields serialized as extension members, with their member names.
+    extensions: Vec<(FieldRef, LitStr)>,
+    /// Fields passed as localization arguments, with their argument names.
+    message_args: Vec<(FieldRef, LitStr)>,
+    /// Field serialized as the structured details of the error.
+    body: Option<FieldRef>,
+}
+
+fn parse_field_attrs(variant: &syn::Variant) -> syn::Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+
+    for (index, field) in variant.fields.iter().enumerate() {
+        let field_ref = match &field.ident {
+            Some(ident) => FieldRef::Named(ident.clone()),
+            None => FieldRef::Index(index, proc_macro2::Span::call_site()),
+        };
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("response"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("extension") {
+                    let member = if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse()?
+                    } else if let Some(ident) = &field.ident {
+                        LitStr::new(&ident.to_string(), ident.span())
+                    } else {
+                        return Err(meta.error("tuple fields need a name: `extension = \"...\"`"));
+                    };
+
+                    field_attrs.extensions.push((field_ref.clone(), member));
+                    Ok(())
+                } else if meta.path.is_ident("message_arg") {
+                    let name = if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse()?
+                    } else if let Some(ident) = &field.ident {
+                        LitStr::new(&ident.to_string(), ident.span())
+                    } else {
+                        return Err(meta.error("tuple fields need a name: `message_arg = \"...\"`"));
+                    };
+
+                    field_attrs.message_args.push((field_ref.clone(), name));
+                    Ok(())
+                } else if meta.path.is_ident("body") {
+                    if field_attrs.body.is_some() {
+                        return Err(meta.error("only one field can be marked as the body"));
+                    }
+
+                    field_attrs.body = Some(field_ref.clone());
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported response attribute"))
+                }
+            })?;
+        }
+    }
+
+    Ok(field_attrs)
+}
+
+/// A variant field referenced from an attribute, either by name or by position.
+#[derive(Clone)]
+enum FieldRef {
+    Named(syn::Ident),
+    Index(usize, proc_macro2::Span),
+}
+
+impl FieldRef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            Ok(FieldRef::Named(lit.parse()?))
+        } else {
+            let lit: syn::LitInt = input.parse()?;
+            Ok(FieldRef::Index(lit.base10_parse()?, lit.span()))
+        }
+    }
+
+    fn error(&self, message: &str) -> syn::Error {
+        match self {
+            FieldRef::Named(ident) => syn::Error::new(ident.span(), message),
+            FieldRef::Index(_, span) => syn::Error::new(*span, message),
+        }
+    }
+}
+
+fn parse_response_attrs(attrs: &[Attribute]) -> syn::Result<ResponseAttrs> {
+    let mut response = ResponseAttrs::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("response")) {
+        attr.parse_nested_meta(|meta| {
+            response.keys.push(meta.path.clone());
+
+            if meta.path.is_ident("nested") || meta.path.is_ident("nested_or_map") {
+                if response.nested.is_some() {
+                    return Err(meta.error("only one of `nested` and `nested_or_map` can be set"));
+                }
+
+                response.nested = Some(match meta.path.is_ident("nested") {
+                    true => Nested::Context,
+                    false => Nested::OrMap,
+                });
+
+                if meta.input.peek(syn::Token![=]) {
+                    set_nested_field(&mut response, &meta)?;
+                }
+                Ok(())
+            } else if meta.path.is_ident("nested_field") {
+                set_nested_field(&mut response, &meta)?;
+                Ok(())
+            } else if meta.path.is_ident("code_prefix") {
+                response.code_prefix = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("map") {
+                response.map = Some(parse_path(meta.value()?)?);
+                Ok(())
+            } else if meta.path.is_ident("fallback_status") {
+                response.fallback_status = Some(parse_status_value(meta.value()?)?);
+                Ok(())
+            } else if meta.path.is_ident("fallback_code") {
+                response.fallback_code = Some(parse_code_value(meta.value()?)?);
+                Ok(())
+            } else if meta.path.is_ident("status_code") {
+                response.status_code = Some(parse_status_value(meta.value()?)?);
+                Ok(())
+            } else if meta.path.is_ident("code") {
+                response.code = Some(parse_code_value(meta.value()?)?);
+                Ok(())
+            } else if meta.path.is_ident("type") {
+                response.problem_type = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("title") {
+                response.title = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("message_key") {
+                response.message_key = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("help") {
+                response.help = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("instance") {
+                response.instance = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("content_type") {
+                response.content_type = Some(parse_content_type(meta.value()?)?);
+                Ok(())
+            } else if meta.path.is_ident("format") {
+                response.format = Some(parse_format(&meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("log") {
+                let level: LitStr = meta.value()?.parse()?;
+                response.log = Some(match level.value().as_str() {
+                    "off" => Log::Off,
+                    _ => Log::Level(parse_severity(&level).map_err(|_| {
+                        syn::Error::new(
+                            level.span(),
+                            "unknown log level, expected \"error\", \"warn\", \"info\" or \"off\"",
+                        )
+                    })?),
+                });
+                Ok(())
+            } else if meta.path.is_ident("severity") {
+                response.severity = Some(parse_severity(&meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("internal") {
+                response.internal = true;
+                Ok(())
+            } else if meta.path.is_ident("header") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let name: LitStr = content.parse()?;
+                content.parse::<syn::Token![,]>()?;
+                let value: LitStr = content.parse()?;
+
+                validate_header(&name, &value)?;
+
+                response.headers.push((name, value));
+                Ok(())
+            } else if meta.path.is_ident("retry_after") {
+                let secs: syn::LitInt = meta.value()?.parse()?;
+                secs.base10_parse::<u64>()?;
+                response.retry_after = Some(RetryAfter::Seconds(secs));
+                Ok(())
+            } else if meta.path.is_ident("retry_after_field") {
+                let field = FieldRef::parse(meta.value()?)?;
+                response.retry_after = Some(RetryAfter::Field(field));
+                Ok(())
+            } else if meta.path.is_ident("status_from") {
+                response.status_from = Some(FieldRef::parse(meta.value()?)?);
+                Ok(())
+            } else if meta.path.is_ident("code_from") {
+                response.code_from = Some(FieldRef::parse(meta.value()?)?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported response attribute"))
+            }
+        })?;
+    }
+
+    Ok(response)
+}
+
+/// Parses the field selected by `nested = ...` or `nested_field = ...`.
+fn set_nested_field(
+    response: &mut ResponseAttrs,
+    meta: &syn::meta::ParseNestedMeta,
+) -> syn::Result<()> {
+    if response.nested_field.is_some() {
+        return Err(meta.error("the nested field is already selected"));
+    }
+
+    response.nested_field = Some(FieldRef::parse(meta.value()?)?);
+    Ok(())
+}
+
+/// Rejects header names and values that `http` would refuse at runtime, so the
+/// mistake surfaces at compile time instead of as a panic in `into_response`.
+fn validate_header(name: &LitStr, value: &LitStr) -> syn::Result<()> {
+    let name_str = name.value();
+    let is_token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+
+    if name_str.is_empty() || !name_str.chars().all(is_token) {
+        return Err(syn::Error::new(name.span(), "invalid header name"));
+    }
+
+    if !value
+        .value()
+        .chars()
+        .all(|c| c == '\t' || (' '..='~').contains(&c))
+    {
+        return Err(syn::Error::new(value.span(), "invalid header value"));
+    }
+
+    Ok(())
+}
+
+/// Builds the match pattern for a variant, binding the referenced fields to the
+/// given identifiers and ignoring everything else.
+///
+/// A field may be referenced more than once; later bindings are returned as
+/// `let` aliases of the first one, to be emitted at the start of the arm.
+fn variant_pattern(
+    name: &syn::Ident,
+    variant: &syn::Variant,
+    bindings: &[(FieldRef, syn::Ident)],
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let ident = &variant.ident;
+    let mut aliases = Vec::new();
+
+    let pattern = match &variant.fields {
+        Fields::Unit => {
+            if let Some((field, _)) = bindings.first() {
+                return Err(field.error("unit variants have no fields"));
+            }
+
+            quote! { #name::#ident }
+        }
+        Fields::Named(fields) => {
+            let mut bound: Vec<(&syn::Ident, &syn::Ident)> = Vec::new();
+
+            for (field, binding) in bindings {
+                let field_ident = match field {
+                    FieldRef::Named(field_ident) => field_ident,
+                    FieldRef::Index(..) => {
+                        return Err(field.error("named variants must reference fields by name"))
+                    }
+                };
+
+                if !fields
+                    .named
+                    .iter()
+                    .any(|f| f.ident.as_ref() == Some(field_ident))
+                {
+                    return Err(field.error("no such field on this variant"));
+                }
+
+                match bound
+                    .iter()
+                    .find(|(bound_field, _)| *bound_field == field_ident)
+                {
+                    Some((_, first)) => aliases.push(quote! { let #binding = #first; }),
+                    None => bound.push((field_ident, binding)),
+                }
+            }
+
+            let bound = bound
+                .iter()
+                .map(|(field_ident, binding)| quote! { #field_ident: #binding });
+
+            quote! { #name::#ident { #(#bound,)* .. } }
+        }
+        Fields::Unnamed(fields) => {
+            let mut slots: Vec<Option<&syn::Ident>> = fields.unnamed.iter().map(|_| None).collect();
+
+            for (field, binding) in bindings {
+                let index = match field {
+                    FieldRef::Index(index, _) => *index,
+                    FieldRef::Named(_) => {
+                        return Err(field.error("tuple variants must reference fields by index"))
+                    }
+                };
+
+                match slots.get_mut(index) {
+                    Some(Some(first)) => aliases.push(quote! { let #binding = #first; }),
+                    Some(slot) => *slot = Some(binding),
+                    None => return Err(field.error("no such field on this variant")),
+                }
+            }
+
+            let slots = slots.iter().map(|slot| match slot {
+                Some(binding) => quote! { #binding },
+                None => quote! { _ },
+            });
+
+            quote! { #name::#ident(#(#slots),*) }
+        }
+    };
+
+    Ok((pattern, quote! { #(#aliases)* }))
+}
@@ -0,0 +1,38 @@
+//! actix-web glue: the derive implements `actix_web::ResponseError` with the
+//! same bodies as the axum `IntoResponse` impl. actix-web is built on the
+//! `http` 0.2 types, so the rendered response is copied over.
+
+use actix_web::{
+    http::{
+        header::{HeaderName, HeaderValue},
+        StatusCode,
+    },
+    HttpResponse,
+};
+
+use crate::ErrorResponseContext;
+
+impl ErrorResponseContext {
+    /// Renders like [`into_http_response`](Self::into_http_response) into an
+    /// actix-web response.
+    pub fn into_actix_response(self) -> HttpResponse {
+        let (parts, body) = self.into_http_response().into_parts();
+        let mut builder = HttpResponse::build(actix_status(parts.status));
+
+        for (name, value) in &parts.headers {
+            let name = HeaderName::from_bytes(name.as_str().as_bytes());
+            let value = HeaderValue::from_bytes(value.as_bytes());
+
+            if let (Ok(name), Ok(value)) = (name, value) {
+                builder.append_header((name, value));
+            }
+        }
+
+        builder.body(body)
+    }
+}
+
+/// The actix-web status with the same code, used by the generated `status_code`.
+pub fn actix_status(status: http::StatusCode) -> StatusCode {
+    StatusCode::from_u16(status.as_u16()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
@@ -0,0 +1,59 @@
+//! Responses for `anyhow::Error`, found by walking its chain for an error
+//! type registered with [`register_error_type`](crate::register_error_type)
+//! or [`register_error_mapper`](crate::register_error_mapper).
+
+use std::fmt;
+
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+
+use crate::{
+    mapping::{fallback_context, map_chain},
+    ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+/// Wraps an [`anyhow::Error`] so handlers can use `?` on any error. The
+/// response is the context of the first registered error in the chain, from
+/// the outermost context to the root cause, and the fallback set with
+/// [`set_fallback_mapper`](crate::set_fallback_mapper) otherwise.
+///
+/// Like `anyhow::Error`, it does not implement `std::error::Error`, so that
+/// every error converts into it.
+pub struct AnyhowError(pub anyhow::Error);
+
+impl<E: Into<anyhow::Error>> From<E> for AnyhowError {
+    fn from(err: E) -> Self {
+        Self(err.into())
+    }
+}
+
+impl fmt::Debug for AnyhowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for AnyhowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToErrorResponseContext for AnyhowError {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        map_chain(self.0.chain()).unwrap_or_else(|| fallback_context(self.0.as_ref()))
+    }
+}
+
+impl IntoErrorResponseContext for AnyhowError {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for AnyhowError {
+    fn into_response(self) -> Response {
+        self.into_response_context().into_response()
+    }
+}
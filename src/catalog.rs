@@ -0,0 +1,180 @@
+//! A listing of the errors an application can return, built from the variants
+//! of every derived enum.
+
+use std::{collections::BTreeMap, sync::OnceLock};
+
+use serde::Serialize;
+
+use crate::config::INTERNAL_CODE;
+
+/// One variant of a derived error enum, as known at compile time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CatalogEntry {
+    /// The enum's name.
+    pub error: &'static str,
+    /// The variant's name.
+    pub variant: &'static str,
+    /// `None` for variants whose status is read from a field, and for nested
+    /// variants that respond with the wrapped error's status.
+    pub status: Option<u16>,
+    /// `None` for variants whose code is read from a field, and for nested
+    /// variants that respond with the wrapped error's code.
+    pub code: Option<String>,
+    /// The `title` attribute, or the variant's doc comment.
+    pub title: Option<&'static str>,
+    /// Whether clients see a generic code and message instead, see
+    /// `#[response(internal)]`.
+    pub internal: bool,
+}
+
+/// Lists the variants of an error enum, implemented by
+/// [`AxumErrorResponse`](crate::AxumErrorResponse).
+pub trait ErrorCatalog {
+    fn catalog_entries() -> Vec<CatalogEntry>;
+}
+
+/// The variants of a derived enum, submitted by the derive so the [`catalog`]
+/// lists every derived enum linked into the binary.
+#[doc(hidden)]
+pub struct CatalogRegistration {
+    entries: fn() -> Vec<CatalogEntry>,
+}
+
+impl CatalogRegistration {
+    pub const fn new(entries: fn() -> Vec<CatalogEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+inventory::collect!(CatalogRegistration);
+
+static CATALOG: OnceLock<Vec<CatalogEntry>> = OnceLock::new();
+
+/// The variants of every derived enum in the binary, e.g. to generate
+/// documentation or serve a listing of the errors an API returns.
+///
+/// Enums are collected at link time, so none can be forgotten. They are
+/// ordered by name, and their variants in declaration order.
+pub fn catalog() -> Vec<CatalogEntry> {
+    CATALOG
+        .get_or_init(|| {
+            let mut enums = inventory::iter::<CatalogRegistration>
+                .into_iter()
+                .map(|registration| (registration.entries)())
+                .collect::<Vec<_>>();
+
+            enums.sort_by_key(|entries| entries.first().map(|entry| entry.error));
+            enums.into_iter().flatten().collect()
+        })
+        .clone()
+}
+
+/// The codes used by more than one variant of the [`catalog`], sorted, so a
+/// test can assert that none are shared by accident.
+pub fn duplicate_codes() -> Vec<String> {
+    let mut counts = BTreeMap::<String, usize>::new();
+
+    for code in catalog().into_iter().filter_map(|entry| entry.code) {
+        *counts.entry(code).or_default() += 1;
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(code, _)| code)
+        .collect()
+}
+
+/// The codes clients can receive from the catalog's variants, sorted, with the
+/// generic code of internal variants. `None` when the catalog is empty or a
+/// code is only known at runtime.
+pub(crate) fn client_codes() -> Option<Vec<String>> {
+    let mut codes = catalog()
+        .into_iter()
+        .map(|entry| match entry.internal {
+            true => Some(INTERNAL_CODE.to_string()),
+            false => entry.code,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    codes.sort();
+    codes.dedup();
+    Some(codes).filter(|codes| !codes.is_empty())
+}
+
+/// The codes of the contexts the crate builds itself, with whether the
+/// feature that builds them is enabled.
+const BUILTIN_CODES: &[(bool, &[&str])] = &[
+    // Unmapped errors, e.g. the field of a `nested_or_map` variant.
+    (true, &["INTERNAL_SERVER_ERROR"]),
+    (
+        cfg!(feature = "axum"),
+        &["INTERNAL_PANIC", "METHOD_NOT_ALLOWED", "ROUTE_NOT_FOUND"],
+    ),
+    (
+        cfg!(feature = "tower"),
+        &["REQUEST_TIMEOUT", "SERVICE_UNAVAILABLE"],
+    ),
+    (
+        cfg!(feature = "rejections"),
+        &[
+            "INVALID_JSON_BODY",
+            "INVALID_JSON_DATA",
+            "INVALID_PATH_PARAMS",
+            "INVALID_QUERY_STRING",
+            "INVALID_REQUEST_BODY",
+            "MISSING_EXTENSION",
+            "MISSING_JSON_CONTENT_TYPE",
+            "MISSING_PATH_PARAMS",
+        ],
+    ),
+    (
+        cfg!(feature = "axum-extra"),
+        &[
+            "INVALID_FORM_DATA",
+            "INVALID_HEADER",
+            "MISSING_FORM_CONTENT_TYPE",
+            "MISSING_HEADER",
+        ],
+    ),
+    (
+        cfg!(feature = "multipart"),
+        &["MULTIPART_INVALID", "PAYLOAD_TOO_LARGE"],
+    ),
+    (cfg!(feature = "validator"), &["VALIDATION_ERROR"]),
+    (
+        cfg!(feature = "jsonwebtoken"),
+        &["TOKEN_EXPIRED", "TOKEN_INVALID", "UNAUTHORIZED"],
+    ),
+    (
+        cfg!(any(
+            feature = "sqlx",
+            feature = "diesel",
+            feature = "sea-orm"
+        )),
+        &[
+            "CONFLICT",
+            "NOT_FOUND",
+            "SERVICE_UNAVAILABLE",
+            "UNPROCESSABLE_ENTITY",
+        ],
+    ),
+    (cfg!(feature = "redis"), &["SERVICE_UNAVAILABLE"]),
+];
+
+/// The codes clients can receive: those of [`client_codes`], and those of the
+/// contexts the crate builds for the enabled features, such as the fallback's
+/// `ROUTE_NOT_FOUND`, sorted. `None` when the catalog is empty or a code is
+/// only known at runtime.
+pub(crate) fn response_codes() -> Option<Vec<String>> {
+    let mut codes = client_codes()?;
+    let builtin = BUILTIN_CODES
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .flat_map(|(_, codes)| codes.iter().map(|code| code.to_string()));
+
+    codes.extend(builtin);
+    codes.sort();
+    codes.dedup();
+    Some(codes)
+}
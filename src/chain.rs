@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// One level of a nested error, reported in the `chain` member of the body
+/// when [`set_include_chain`](crate::set_include_chain) is enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainEntry {
+    code: String,
+    message: String,
+}
+
+impl ChainEntry {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
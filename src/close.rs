@@ -0,0 +1,65 @@
+use http::StatusCode;
+
+use crate::{format, ErrorResponseContext};
+
+/// Longest close reason a WebSocket close frame can carry, in bytes.
+const MAX_CLOSE_REASON: usize = 123;
+
+impl ErrorResponseContext {
+    /// WebSocket close code closest to the context's status: `1009` for a
+    /// `413`, `1008` (policy violation) for other client errors, `1013` (try
+    /// again later) for a `503` and `1011` (internal error) otherwise.
+    ///
+    /// With the reason, it builds the close frame of the WebSocket library in
+    /// use, e.g. axum's:
+    ///
+    /// ```rust,ignore
+    /// let ctx = err.into_response_context();
+    /// socket
+    ///     .send(Message::Close(Some(CloseFrame {
+    ///         code: ctx.close_code(),
+    ///         reason: ctx.close_reason().into(),
+    ///     })))
+    ///     .await?;
+    /// ```
+    pub fn close_code(&self) -> u16 {
+        match self.status_code() {
+            StatusCode::PAYLOAD_TOO_LARGE => 1009,
+            status if status.is_client_error() => 1008,
+            StatusCode::SERVICE_UNAVAILABLE => 1013,
+            _ => 1011,
+        }
+    }
+
+    /// `CODE: message`, cut at a character boundary to fit the 123 bytes a
+    /// close frame allows.
+    pub fn close_reason(&self) -> String {
+        let mut reason = format::text_body(self);
+
+        if reason.len() > MAX_CLOSE_REASON {
+            let end = (0..=MAX_CLOSE_REASON)
+                .rev()
+                .find(|&end| reason.is_char_boundary(end))
+                .unwrap_or(0);
+            reason.truncate(end);
+        }
+
+        reason
+    }
+
+    /// axum's WebSocket close frame with the [`close_code`](Self::close_code)
+    /// and [`close_reason`](Self::close_reason), to end a connection with the
+    /// same error enums the HTTP handlers return:
+    ///
+    /// ```rust,ignore
+    /// let frame = err.into_response_context().into_close_frame();
+    /// socket.send(Message::Close(Some(frame))).await?;
+    /// ```
+    #[cfg(all(feature = "axum", feature = "ws"))]
+    pub fn into_close_frame(self) -> axum::extract::ws::CloseFrame<'static> {
+        axum::extract::ws::CloseFrame {
+            code: self.close_code(),
+            reason: self.close_reason().into(),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::{borrow::Cow, env, sync::OnceLock};
+
+#[cfg(feature = "i18n")]
+use crate::i18n::resolve_message;
+use crate::ErrorResponseContext;
+
+static ERROR_CONFIG: OnceLock<ErrorConfig> = OnceLock::new();
+
+/// Sets what error bodies disclose for the whole application, instead of
+/// reading it from the environment, see [`ErrorConfig::from_env`].
+///
+/// Can only be set once, before the first error is rendered; later calls
+/// return the rejected config.
+pub fn set_error_config(config: ErrorConfig) -> Result<(), ErrorConfig> {
+    ERROR_CONFIG.set(config)
+}
+
+/// What error bodies disclose, so development builds can show everything
+/// while production hides internals, without touching the derive attributes.
+///
+/// ```rust,ignore
+/// axum_error_handler::set_error_config(ErrorConfig {
+///     expose_messages: cfg!(debug_assertions),
+///     expose_sources: cfg!(debug_assertions),
+/// })
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorConfig {
+    /// Render the messages of 5xx errors. When off, they are replaced by the
+    /// status's canonical reason, e.g. `Internal Server Error`, and their
+    /// details, extension members, causes and nested chain are left out.
+    pub expose_messages: bool,
+    /// Render the `causes` array, as with
+    /// [`set_include_causes`](crate::set_include_causes), which wins when
+    /// set.
+    pub expose_sources: bool,
+}
+
+impl Default for ErrorConfig {
+    fn default() -> Self {
+        Self {
+            expose_messages: true,
+            expose_sources: false,
+        }
+    }
+}
+
+impl ErrorConfig {
+    /// The defaults, overridden by the `AXUM_ERROR_EXPOSE_MESSAGES` and
+    /// `AXUM_ERROR_EXPOSE_SOURCES` environment variables, set to `true`/`1`
+    /// or `false`/`0`.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            expose_messages: env_flag("AXUM_ERROR_EXPOSE_MESSAGES")
+                .unwrap_or(defaults.expose_messages),
+            expose_sources: env_flag("AXUM_ERROR_EXPOSE_SOURCES")
+                .unwrap_or(defaults.expose_sources),
+        }
+    }
+
+    /// The configuration set with [`set_error_config`], or read from the
+    /// environment on first use.
+    pub fn global() -> Self {
+        *ERROR_CONFIG.get_or_init(Self::from_env)
+    }
+}
+
+fn env_flag(name: &str) -> Option<bool> {
+    match env::var(name).ok()?.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" => Some(true),
+        "0" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Code sent to clients in place of the code of an internal error.
+pub(crate) const INTERNAL_CODE: &str = "INTERNAL_ERROR";
+
+/// Message sent to clients in place of the message of an internal error.
+const INTERNAL_MESSAGE: &str = "An internal error occurred";
+
+/// The code sent to clients: the context's own, or a generic one for an
+/// internal error.
+pub(crate) fn client_code(ctx: &ErrorResponseContext) -> &str {
+    match ctx.is_internal() {
+        true => INTERNAL_CODE,
+        false => ctx.code(),
+    }
+}
+
+/// The message sent to clients: the context's own or its localization, a
+/// generic one for an internal error, or for a 5xx with `expose_messages` off,
+/// the status's canonical reason.
+pub(crate) fn client_message(ctx: &ErrorResponseContext) -> Cow<'_, str> {
+    let status = ctx.status_code();
+
+    if ctx.is_internal() {
+        INTERNAL_MESSAGE.into()
+    } else if status.is_server_error() && !ErrorConfig::global().expose_messages {
+        status
+            .canonical_reason()
+            .unwrap_or("Internal Server Error")
+            .into()
+    } else {
+        match resolve_message(ctx) {
+            Some(message) => message.into(),
+            None => ctx.message().into(),
+        }
+    }
+}
+
+/// The title sent to clients: the context's own, but none for an internal
+/// error, whose title describes the variant.
+pub(crate) fn client_title(ctx: &ErrorResponseContext) -> Option<&str> {
+    ctx.title().filter(|_| !ctx.is_internal())
+}
+
+/// The help link sent to clients: the context's own, but none for an
+/// internal error, whose link points at the variant's documentation.
+pub(crate) fn client_help_url(ctx: &ErrorResponseContext) -> Option<&str> {
+    ctx.help_url().filter(|_| !ctx.is_internal())
+}
+
+/// Whether the members describing the error beyond its code and message, the
+/// details, extension members, causes and nested chain, are sent to clients:
+/// not for an internal error, nor for a 5xx with `expose_messages` off.
+pub(crate) fn exposes_details(ctx: &ErrorResponseContext) -> bool {
+    if ctx.is_internal() {
+        return false;
+    }
+
+    !ctx.status_code().is_server_error() || ErrorConfig::global().expose_messages
+}
+
+#[cfg(not(feature = "i18n"))]
+fn resolve_message(_ctx: &ErrorResponseContext) -> Option<String> {
+    None
+}
@@ -0,0 +1,819 @@
+use std::time::{Duration, SystemTime};
+#[cfg(feature = "backtrace")]
+use std::{backtrace::Backtrace, sync::Arc};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[cfg(feature = "axum")]
+use axum::{
+    body::Body,
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use http::{header, Extensions, HeaderMap, HeaderName, HeaderValue, StatusCode};
+
+mod serialize;
+
+use crate::{
+    current_request,
+    format::{self, ErrorFormat},
+    request::current_trace_id,
+    BodyEncoding, ChainEntry, EnvelopeConfig, FieldError, Severity,
+};
+
+/// Everything needed to render an error response: the status, the
+/// machine-readable code, and the human-readable message.
+///
+/// Equality compares everything that affects the rendered response; the
+/// timestamp, typed extensions and backtrace are ignored.
+#[derive(Debug)]
+pub struct ErrorResponseContext {
+    status_code: StatusCode,
+    code: String,
+    message: String,
+    message_key: Option<String>,
+    message_args: Option<Map<String, Value>>,
+    type_uri: Option<String>,
+    title: Option<String>,
+    type_description: Option<String>,
+    instance: Option<String>,
+    help_url: Option<String>,
+    retry_after: Option<Duration>,
+    format: ErrorFormat,
+    encoding: Option<BodyEncoding>,
+    content_type: Option<String>,
+    extension_members: Map<String, Value>,
+    details: Option<Value>,
+    field_errors: Vec<FieldError>,
+    envelope: Option<EnvelopeConfig>,
+    severity: Option<Severity>,
+    internal: bool,
+    timestamp: SystemTime,
+    trace_id: Option<String>,
+    sources: Vec<String>,
+    chain: Vec<ChainEntry>,
+    #[cfg(feature = "backtrace")]
+    backtrace: Option<Arc<Backtrace>>,
+    headers: HeaderMap,
+    extensions: Extensions,
+}
+
+impl PartialEq for ErrorResponseContext {
+    fn eq(&self, other: &Self) -> bool {
+        self.status_code == other.status_code
+            && self.code == other.code
+            && self.message == other.message
+            && self.message_key == other.message_key
+            && self.message_args == other.message_args
+            && self.type_uri == other.type_uri
+            && self.title == other.title
+            && self.type_description == other.type_description
+            && self.instance == other.instance
+            && self.help_url == other.help_url
+            && self.retry_after == other.retry_after
+            && self.format == other.format
+            && self.encoding == other.encoding
+            && self.content_type == other.content_type
+            && self.extension_members == other.extension_members
+            && self.details == other.details
+            && self.field_errors == other.field_errors
+            && self.envelope == other.envelope
+            && self.severity == other.severity
+            && self.internal == other.internal
+            && self.trace_id == other.trace_id
+            && self.sources == other.sources
+            && self.chain == other.chain
+            && self.headers == other.headers
+    }
+}
+
+impl Eq for ErrorResponseContext {}
+
+impl ErrorResponseContext {
+    pub fn builder() -> ErrorResponseBuilder {
+        ErrorResponseBuilder::new()
+    }
+
+    /// A context for `status` with its canonical reason as the message and
+    /// the reason in upper snake case as the code, e.g. `NOT_FOUND` and
+    /// `Not Found`. Statuses without a reason get `HTTP_<status>`.
+    pub fn from_status(status: StatusCode) -> Self {
+        let (code, message) = match status.canonical_reason() {
+            Some(reason) => {
+                let code = reason
+                    .chars()
+                    .map(|c| match c.is_ascii_alphanumeric() {
+                        true => c.to_ascii_uppercase(),
+                        false => '_',
+                    })
+                    .collect();
+
+                (code, reason.to_string())
+            }
+            None => (
+                format!("HTTP_{}", status.as_u16()),
+                status.as_str().to_string(),
+            ),
+        };
+
+        ErrorResponseBuilder::new()
+            .status_code(status)
+            .code(code)
+            .message(message)
+            .build()
+    }
+
+    pub fn bad_request() -> Self {
+        Self::from_status(StatusCode::BAD_REQUEST)
+    }
+
+    pub fn unauthorized() -> Self {
+        Self::from_status(StatusCode::UNAUTHORIZED)
+    }
+
+    pub fn forbidden() -> Self {
+        Self::from_status(StatusCode::FORBIDDEN)
+    }
+
+    pub fn not_found() -> Self {
+        Self::from_status(StatusCode::NOT_FOUND)
+    }
+
+    pub fn conflict() -> Self {
+        Self::from_status(StatusCode::CONFLICT)
+    }
+
+    pub fn unprocessable_entity() -> Self {
+        Self::from_status(StatusCode::UNPROCESSABLE_ENTITY)
+    }
+
+    pub fn too_many_requests() -> Self {
+        Self::from_status(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    pub fn internal_server_error() -> Self {
+        Self::from_status(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    pub fn service_unavailable() -> Self {
+        Self::from_status(StatusCode::SERVICE_UNAVAILABLE)
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        self.status_code
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Stable localization key for the message, rendered as `message_key` so
+    /// clients or a server-side resolver can translate it.
+    pub fn message_key(&self) -> Option<&str> {
+        self.message_key.as_deref()
+    }
+
+    /// Named values to interpolate into the localized message, rendered as
+    /// `message_args`.
+    pub fn message_args(&self) -> Option<&Map<String, Value>> {
+        self.message_args.as_ref()
+    }
+
+    /// URI identifying the problem type (the RFC 9457 `type` member).
+    pub fn type_uri(&self) -> Option<&str> {
+        self.type_uri.as_deref()
+    }
+
+    /// Short summary of the error kind, taken from the variant's doc comment
+    /// when derived.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Description of the error family, taken from the enum's doc comment
+    /// when derived.
+    pub fn type_description(&self) -> Option<&str> {
+        self.type_description.as_deref()
+    }
+
+    /// URI identifying this specific occurrence of the problem.
+    pub fn instance(&self) -> Option<&str> {
+        self.instance.as_deref()
+    }
+
+    /// Link to documentation on how to resolve the error, rendered as
+    /// `help_url`.
+    pub fn help_url(&self) -> Option<&str> {
+        self.help_url.as_deref()
+    }
+
+    /// How long the client should wait before retrying.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
+    /// [`retry_after`](Self::retry_after) in whole seconds, rounded up, as
+    /// sent in the `Retry-After` header and the body.
+    pub fn retry_after_secs(&self) -> Option<u64> {
+        self.retry_after
+            .map(|delay| delay.as_secs() + u64::from(delay.subsec_nanos() > 0))
+    }
+
+    /// Body layout used by the `IntoResponse` impl.
+    pub fn format(&self) -> ErrorFormat {
+        self.format
+    }
+
+    /// Additional members rendered alongside the standard ones, such as
+    /// `balance` in the RFC 9457 out-of-credit example.
+    pub fn extension_members(&self) -> &Map<String, Value> {
+        &self.extension_members
+    }
+
+    /// Structured payload rendered as `error.details`, merged into the
+    /// problem-details members, or placed under the GraphQL `extensions` and
+    /// JSON:API `meta`.
+    pub fn details(&self) -> Option<&Value> {
+        self.details.as_ref()
+    }
+
+    /// Per-field problems, rendered as the `fields` member.
+    pub fn field_errors(&self) -> &[FieldError] {
+        &self.field_errors
+    }
+
+    /// Envelope key names; `None` means the global configuration is used.
+    pub fn envelope(&self) -> Option<EnvelopeConfig> {
+        self.envelope
+    }
+
+    /// Body encoding; `None` means it is negotiated from the request's
+    /// `Accept` header, falling back to JSON.
+    pub fn encoding(&self) -> Option<BodyEncoding> {
+        self.encoding
+    }
+
+    /// Content type override; `None` means the format's default is used.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// How loudly the error should be logged. Falls back to
+    /// [`Severity::for_status`] when none was declared.
+    pub fn severity(&self) -> Severity {
+        self.severity
+            .unwrap_or_else(|| Severity::for_status(self.status_code))
+    }
+
+    /// Whether the error is internal: bodies then show a generic code and
+    /// message, while these accessors and logging hooks keep the real ones.
+    pub fn is_internal(&self) -> bool {
+        self.internal
+    }
+
+    /// When the context was built. Only rendered once
+    /// [`set_include_timestamp`](crate::set_include_timestamp) is enabled.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Id that maps the error to server traces, rendered as `trace_id`.
+    /// Unless set on the builder, it is the trace id of the request's W3C
+    /// `traceparent` header, otherwise its `x-request-id` header, and only
+    /// known while running under
+    /// [`RequestContextLayer`](crate::RequestContextLayer).
+    pub fn trace_id(&self) -> Option<&str> {
+        self.trace_id.as_deref()
+    }
+
+    /// Messages of the error's `source()` chain, outermost first. Only
+    /// rendered once [`set_include_causes`](crate::set_include_causes) is
+    /// enabled.
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// Code and message of every level of a nested error, from the outermost
+    /// variant to the leaf. Only rendered once
+    /// [`set_include_chain`](crate::set_include_chain) is enabled.
+    pub fn chain(&self) -> &[ChainEntry] {
+        &self.chain
+    }
+
+    /// Backtrace captured when the error was converted. Never rendered; meant
+    /// for logging server errors.
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_deref()
+    }
+
+    /// Headers applied to the response. They replace any header of the same
+    /// name set while rendering, such as `Content-Type` or `Retry-After`.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Typed values attached while building or handling the error, such as
+    /// a tenant id or locale. They are not rendered, but are moved into the
+    /// response's extensions.
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.extensions
+    }
+}
+
+impl ErrorResponseContext {
+    /// Renders the body and picks its content type. `accept` is the request's
+    /// `Accept` header, consulted when neither the format nor the encoding
+    /// is fixed.
+    fn render_body(&self, accept: Option<&str>) -> (Vec<u8>, HeaderValue) {
+        if self.format == ErrorFormat::Text {
+            return (
+                format::text_body(self).into_bytes(),
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            );
+        }
+
+        #[cfg(feature = "html")]
+        if self.format == ErrorFormat::Html
+            || (self.encoding.is_none() && accept.is_some_and(crate::html::prefers_html))
+        {
+            return (
+                crate::html::render(self).into_bytes(),
+                HeaderValue::from_static("text/html; charset=utf-8"),
+            );
+        }
+
+        let encoding = self
+            .encoding
+            .or_else(|| accept.and_then(BodyEncoding::negotiate))
+            .unwrap_or(BodyEncoding::Json);
+        let body = format::structured_body(self);
+
+        (
+            encoding.encode(&body, self.format),
+            encoding.content_type(self.format),
+        )
+    }
+
+    /// Combines two contexts, typically a parent error's with the context of
+    /// the error it wraps. Everything set on `self` wins; `fallback` only
+    /// fills in what `self` leaves unset:
+    ///
+    /// - status and format are always kept from `self`;
+    /// - the context is internal when either one is;
+    /// - code and message are taken from `fallback` when empty on `self`;
+    /// - optional members, sources and headers are taken from `fallback` when
+    ///   absent on `self`, header by header;
+    /// - extension members and typed extensions are combined, `self` winning
+    ///   on clashes.
+    pub fn merge(mut self, fallback: ErrorResponseContext) -> Self {
+        if self.code.is_empty() {
+            self.code = fallback.code;
+        }
+        if self.message.is_empty() {
+            self.message = fallback.message;
+        }
+
+        self.message_key = self.message_key.or(fallback.message_key);
+        self.message_args = self.message_args.or(fallback.message_args);
+        self.type_uri = self.type_uri.or(fallback.type_uri);
+        self.title = self.title.or(fallback.title);
+        self.type_description = self.type_description.or(fallback.type_description);
+        self.instance = self.instance.or(fallback.instance);
+        self.help_url = self.help_url.or(fallback.help_url);
+        self.retry_after = self.retry_after.or(fallback.retry_after);
+        self.encoding = self.encoding.or(fallback.encoding);
+        self.content_type = self.content_type.or(fallback.content_type);
+        self.details = self.details.or(fallback.details);
+        self.envelope = self.envelope.or(fallback.envelope);
+        self.severity = self.severity.or(fallback.severity);
+        self.internal |= fallback.internal;
+        self.trace_id = self.trace_id.or(fallback.trace_id);
+
+        #[cfg(feature = "backtrace")]
+        {
+            self.backtrace = self.backtrace.or(fallback.backtrace);
+        }
+
+        if self.field_errors.is_empty() {
+            self.field_errors = fallback.field_errors;
+        }
+        if self.sources.is_empty() {
+            self.sources = fallback.sources;
+        }
+        if self.chain.is_empty() {
+            self.chain = fallback.chain;
+        }
+
+        for (name, value) in fallback.extension_members {
+            self.extension_members.entry(name).or_insert(value);
+        }
+
+        for name in fallback.headers.keys() {
+            if !self.headers.contains_key(name) {
+                for value in fallback.headers.get_all(name) {
+                    self.headers.append(name.clone(), value.clone());
+                }
+            }
+        }
+
+        let mut extensions = fallback.extensions;
+        extensions.extend(self.extensions);
+        self.extensions = extensions;
+
+        self
+    }
+
+    /// Selectively overrides members through the builder; whatever the
+    /// closure sets wins over the current values.
+    ///
+    /// ```rust,ignore
+    /// let ctx = child.into_response_context().override_with(|builder| {
+    ///     builder
+    ///         .status_code(StatusCode::BAD_GATEWAY)
+    ///         .code_prefix("BILLING_")
+    /// });
+    /// ```
+    pub fn override_with(
+        self,
+        overrides: impl FnOnce(ErrorResponseBuilder) -> ErrorResponseBuilder,
+    ) -> Self {
+        overrides(self.into()).build()
+    }
+
+    /// Renders as `CODE: message` with a `text/plain` content type,
+    /// regardless of the context's format.
+    #[cfg(feature = "axum")]
+    pub fn into_text_response(mut self) -> Response {
+        self.format = ErrorFormat::Text;
+        self.into_default_response()
+    }
+
+    /// Renders with the built-in renderer, bypassing the one registered with
+    /// [`set_default_renderer`](crate::set_default_renderer), then applies the
+    /// hook set with [`set_response_hook`](crate::set_response_hook).
+    #[cfg(feature = "axum")]
+    pub fn into_default_response(self) -> Response {
+        let response = self.render_http_response().map(Body::from);
+
+        match crate::renderer::response_hook() {
+            Some(hook) => hook(&self, response),
+            None => response,
+        }
+    }
+
+    /// Renders with the built-in renderer into a plain `http` response, for
+    /// services that are not built on axum, e.g. on hyper or tower alone.
+    /// The body is negotiated against the `Accept` header of the request
+    /// captured by [`RequestContextLayer`](crate::RequestContextLayer), if
+    /// any.
+    pub fn into_http_response(self) -> http::Response<Bytes> {
+        self.render_http_response()
+    }
+
+    fn render_http_response(&self) -> http::Response<Bytes> {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_response(self);
+
+        let request = current_request();
+        let accept = request
+            .as_ref()
+            .and_then(|request| request.headers().get(header::ACCEPT)?.to_str().ok());
+        let (body, default_content_type) = self.render_body(accept);
+
+        let content_type = self
+            .content_type
+            .as_deref()
+            .and_then(|content_type| HeaderValue::from_str(content_type).ok())
+            .unwrap_or(default_content_type);
+
+        let mut response = http::Response::new(Bytes::from(body));
+        *response.status_mut() = self.status_code;
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type);
+
+        if let Some(secs) = self.retry_after_secs() {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+
+        response.extensions_mut().extend(self.extensions.clone());
+
+        if !self.headers.is_empty() {
+            response.headers_mut().extend(self.headers.clone());
+        }
+
+        response
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for ErrorResponseContext {
+    fn into_response(self) -> Response {
+        match crate::renderer::default_renderer() {
+            Some(renderer) => renderer(self),
+            None => self.into_default_response(),
+        }
+    }
+}
+
+/// Builder for [`ErrorResponseContext`]. Unset fields default to a `500` with
+/// empty code and message.
+#[derive(Debug)]
+pub struct ErrorResponseBuilder {
+    context: ErrorResponseContext,
+}
+
+impl ErrorResponseBuilder {
+    pub fn new() -> Self {
+        Self {
+            context: ErrorResponseContext {
+                status_code: StatusCode::INTERNAL_SERVER_ERROR,
+                code: String::new(),
+                message: String::new(),
+                message_key: None,
+                message_args: None,
+                type_uri: None,
+                title: None,
+                type_description: None,
+                instance: None,
+                help_url: None,
+                retry_after: None,
+                format: ErrorFormat::Envelope,
+                encoding: None,
+                content_type: None,
+                extension_members: Map::new(),
+                details: None,
+                field_errors: Vec::new(),
+                envelope: None,
+                severity: None,
+                internal: false,
+                timestamp: SystemTime::now(),
+                trace_id: current_trace_id(),
+                sources: Vec::new(),
+                chain: Vec::new(),
+                #[cfg(feature = "backtrace")]
+                backtrace: None,
+                headers: HeaderMap::new(),
+                extensions: Extensions::new(),
+            },
+        }
+    }
+
+    pub fn status_code(mut self, status_code: StatusCode) -> Self {
+        self.context.status_code = status_code;
+        self
+    }
+
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.context.code = code.into();
+        self
+    }
+
+    /// Prepends `prefix` to the code set so far.
+    pub fn code_prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        self.context.code.insert_str(0, prefix.as_ref());
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.context.message = message.into();
+        self
+    }
+
+    pub fn message_key(mut self, message_key: impl Into<String>) -> Self {
+        self.context.message_key = Some(message_key.into());
+        self
+    }
+
+    /// Adds a named value for the localized message.
+    pub fn message_arg(mut self, name: impl Into<String>, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).unwrap_or(Value::Null);
+        self.context
+            .message_args
+            .get_or_insert_with(Map::new)
+            .insert(name.into(), value);
+        self
+    }
+
+    pub fn type_uri(mut self, type_uri: impl Into<String>) -> Self {
+        self.context.type_uri = Some(type_uri.into());
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.context.title = Some(title.into());
+        self
+    }
+
+    pub fn type_description(mut self, type_description: impl Into<String>) -> Self {
+        self.context.type_description = Some(type_description.into());
+        self
+    }
+
+    pub fn instance(mut self, instance: impl Into<String>) -> Self {
+        self.context.instance = Some(instance.into());
+        self
+    }
+
+    pub fn help_url(mut self, help_url: impl Into<String>) -> Self {
+        self.context.help_url = Some(help_url.into());
+        self
+    }
+
+    /// Sets the `Retry-After` header, rounded up to whole seconds, and the
+    /// `retry_after` body member.
+    pub fn retry_after(mut self, delay: Duration) -> Self {
+        self.context.retry_after = Some(delay);
+        self
+    }
+
+    pub fn format(mut self, format: ErrorFormat) -> Self {
+        self.context.format = format;
+        self
+    }
+
+    pub fn encoding(mut self, encoding: BodyEncoding) -> Self {
+        self.context.encoding = Some(encoding);
+        self
+    }
+
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.context.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Adds an extension member to the body. Members whose name clashes with a
+    /// standard member are ignored when rendering.
+    pub fn extension_member(mut self, name: impl Into<String>, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).unwrap_or(Value::Null);
+        self.context.extension_members.insert(name.into(), value);
+        self
+    }
+
+    /// Attaches a structured payload, such as a list of invalid fields.
+    pub fn details(mut self, details: impl Serialize) -> Self {
+        self.context.details = serde_json::to_value(details).ok();
+        self
+    }
+
+    /// Reports a problem with a single input field.
+    pub fn field_error(
+        mut self,
+        field: impl Into<String>,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.context
+            .field_errors
+            .push(FieldError::new(field, code, message));
+        self
+    }
+
+    pub fn field_errors(mut self, field_errors: impl IntoIterator<Item = FieldError>) -> Self {
+        self.context.field_errors.extend(field_errors);
+        self
+    }
+
+    pub fn envelope(mut self, envelope: EnvelopeConfig) -> Self {
+        self.context.envelope = Some(envelope);
+        self
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.context.severity = Some(severity);
+        self
+    }
+
+    /// Marks the error as internal, see
+    /// [`ErrorResponseContext::is_internal`].
+    pub fn internal(mut self) -> Self {
+        self.context.internal = true;
+        self
+    }
+
+    /// Overrides the time the error occurred, which defaults to when the
+    /// builder was created.
+    pub fn timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.context.timestamp = timestamp;
+        self
+    }
+
+    pub fn trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.context.trace_id = Some(trace_id.into());
+        self
+    }
+
+    pub fn sources(mut self, sources: Vec<String>) -> Self {
+        self.context.sources = sources;
+        self
+    }
+
+    /// Sets the levels of a nested error, outermost first.
+    pub fn chain(mut self, chain: Vec<ChainEntry>) -> Self {
+        self.context.chain = chain;
+        self
+    }
+
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(mut self, backtrace: Backtrace) -> Self {
+        self.context.backtrace = Some(Arc::new(backtrace));
+        self
+    }
+
+    /// Adds a response header. Repeating a name sends every value.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.context.headers.append(name, value);
+        self
+    }
+
+    /// Attaches a typed value, retrievable through
+    /// [`ErrorResponseContext::extensions`].
+    pub fn extension<T: Clone + Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.context.extensions.insert(value);
+        self
+    }
+
+    pub fn build(self) -> ErrorResponseContext {
+        self.context
+    }
+}
+
+/// Starts from an existing context, keeping all of its members.
+impl From<ErrorResponseContext> for ErrorResponseBuilder {
+    fn from(context: ErrorResponseContext) -> Self {
+        Self { context }
+    }
+}
+
+impl Default for ErrorResponseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Conversion into an [`ErrorResponseContext`], implemented by
+/// `#[derive(AxumErrorResponse)]`.
+pub trait IntoErrorResponseContext {
+    fn into_response_context(self) -> ErrorResponseContext;
+}
+
+impl IntoErrorResponseContext for ErrorResponseContext {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self
+    }
+}
+
+/// Conversion of a borrowed error into an [`ErrorResponseContext`],
+/// implemented by `#[derive(AxumErrorResponse)]`. It lets shared errors, such
+/// as an `Arc<E>` or a `&'static E`, respond without being consumed.
+pub trait ToErrorResponseContext {
+    fn to_response_context(&self) -> ErrorResponseContext;
+}
+
+macro_rules! impl_to_error_response_context {
+    ($($pointer:ty),*) => {
+        $(
+            impl<T: ToErrorResponseContext + ?Sized> ToErrorResponseContext for $pointer {
+                fn to_response_context(&self) -> ErrorResponseContext {
+                    (**self).to_response_context()
+                }
+            }
+        )*
+    };
+}
+
+impl_to_error_response_context!(&T, Box<T>, std::sync::Arc<T>, std::rc::Rc<T>);
+
+impl<T: ToErrorResponseContext + ?Sized> IntoErrorResponseContext for &T {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
+
+impl<T: ToErrorResponseContext + ?Sized> IntoErrorResponseContext for std::sync::Arc<T> {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
+
+impl<T: ToErrorResponseContext + ?Sized> IntoErrorResponseContext for std::rc::Rc<T> {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
@@ -0,0 +1,164 @@
+//! `Serialize`/`Deserialize` for [`ErrorResponseContext`], so a context can be
+//! forwarded between services or persisted and rendered later.
+//!
+//! The context is mapped onto [`SerializedContext`], whose field names are
+//! the stable wire layout. Typed extensions, the backtrace and the envelope
+//! member names are not serialized.
+
+use std::time::{Duration, SystemTime};
+
+use http::{Extensions, HeaderMap, HeaderName, HeaderValue, StatusCode};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use super::ErrorResponseContext;
+use crate::{BodyEncoding, ChainEntry, ErrorFormat, FieldError, Severity};
+
+#[derive(Serialize, Deserialize)]
+struct SerializedContext {
+    status: u16,
+    code: String,
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message_args: Option<Map<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    type_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    type_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    instance: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    help_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry_after: Option<Duration>,
+    #[serde(default)]
+    format: ErrorFormat,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encoding: Option<BodyEncoding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    extension_members: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    details: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    field_errors: Vec<FieldError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    severity: Option<Severity>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    internal: bool,
+    /// RFC 3339, with nanosecond precision.
+    timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    trace_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sources: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    chain: Vec<ChainEntry>,
+    /// Name and value pairs; values that are not visible ASCII are dropped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    headers: Vec<(String, String)>,
+}
+
+/// Typed extensions, the backtrace and the [`EnvelopeConfig`] set with
+/// `#[response(envelope(...))]` or [`ErrorResponseBuilder::envelope`] are not
+/// serialized. Its member names are `&'static str`, which a deserialized
+/// context could only get by leaking client-controlled strings, so a restored
+/// context renders with the global envelope layout instead.
+///
+/// [`EnvelopeConfig`]: crate::EnvelopeConfig
+/// [`ErrorResponseBuilder::envelope`]: crate::ErrorResponseBuilder::envelope
+impl Serialize for ErrorResponseContext {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let headers = self
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+
+        SerializedContext {
+            status: self.status_code.as_u16(),
+            code: self.code.clone(),
+            message: self.message.clone(),
+            message_key: self.message_key.clone(),
+            message_args: self.message_args.clone(),
+            type_uri: self.type_uri.clone(),
+            title: self.title.clone(),
+            type_description: self.type_description.clone(),
+            instance: self.instance.clone(),
+            help_url: self.help_url.clone(),
+            retry_after: self.retry_after,
+            format: self.format,
+            encoding: self.encoding,
+            content_type: self.content_type.clone(),
+            extension_members: self.extension_members.clone(),
+            details: self.details.clone(),
+            field_errors: self.field_errors.clone(),
+            severity: self.severity,
+            internal: self.internal,
+            timestamp: humantime::format_rfc3339_nanos(self.timestamp).to_string(),
+            trace_id: self.trace_id.clone(),
+            sources: self.sources.clone(),
+            chain: self.chain.clone(),
+            headers,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorResponseContext {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = SerializedContext::deserialize(deserializer)?;
+
+        let status_code = StatusCode::from_u16(repr.status).map_err(de::Error::custom)?;
+        let timestamp: SystemTime =
+            humantime::parse_rfc3339(&repr.timestamp).map_err(de::Error::custom)?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in repr.headers {
+            headers.append(
+                HeaderName::try_from(name).map_err(de::Error::custom)?,
+                HeaderValue::try_from(value).map_err(de::Error::custom)?,
+            );
+        }
+
+        Ok(ErrorResponseContext {
+            status_code,
+            code: repr.code,
+            message: repr.message,
+            message_key: repr.message_key,
+            message_args: repr.message_args,
+            type_uri: repr.type_uri,
+            title: repr.title,
+            type_description: repr.type_description,
+            instance: repr.instance,
+            help_url: repr.help_url,
+            retry_after: repr.retry_after,
+            format: repr.format,
+            encoding: repr.encoding,
+            content_type: repr.content_type,
+            extension_members: repr.extension_members,
+            details: repr.details,
+            field_errors: repr.field_errors,
+            envelope: None,
+            severity: repr.severity,
+            internal: repr.internal,
+            timestamp,
+            trace_id: repr.trace_id,
+            sources: repr.sources,
+            chain: repr.chain,
+            #[cfg(feature = "backtrace")]
+            backtrace: None,
+            headers,
+            extensions: Extensions::new(),
+        })
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
@@ -0,0 +1,89 @@
+//! Responses for `diesel` errors, mapped like the `sqlx` ones: constraint
+//! violations and missing rows become client errors, a lost connection a 503,
+//! and everything else a redacted 500.
+
+use std::{error::Error, fmt};
+
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+use diesel::result::{DatabaseErrorKind, Error as DieselResultError};
+use http::StatusCode;
+
+use crate::{
+    sources::{ErrorSources, SourceChain},
+    ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+impl ToErrorResponseContext for DieselResultError {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let status = match self {
+            DieselResultError::NotFound => StatusCode::NOT_FOUND,
+            DieselResultError::DatabaseError(kind, _) => match kind {
+                DatabaseErrorKind::UniqueViolation => StatusCode::CONFLICT,
+                DatabaseErrorKind::ForeignKeyViolation
+                | DatabaseErrorKind::NotNullViolation
+                | DatabaseErrorKind::CheckViolation => StatusCode::UNPROCESSABLE_ENTITY,
+                DatabaseErrorKind::ClosedConnection | DatabaseErrorKind::UnableToSendCommand => {
+                    StatusCode::SERVICE_UNAVAILABLE
+                }
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        // The database's message stays in the sources, which are only
+        // rendered when causes are included.
+        let mut sources = vec![self.to_string()];
+        sources.extend(SourceChain(self).sources());
+
+        ErrorResponseContext::from_status(status).override_with(|builder| builder.sources(sources))
+    }
+}
+
+impl IntoErrorResponseContext for DieselResultError {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
+
+/// Wraps [`diesel::result::Error`] so a handler can return it directly, e.g.
+/// `?` on a query in a handler returning `Result<_, DieselError>`.
+#[derive(Debug)]
+pub struct DieselError(pub DieselResultError);
+
+impl From<DieselResultError> for DieselError {
+    fn from(err: DieselResultError) -> Self {
+        Self(err)
+    }
+}
+
+impl fmt::Display for DieselError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for DieselError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl ToErrorResponseContext for DieselError {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        self.0.to_response_context()
+    }
+}
+
+impl IntoErrorResponseContext for DieselError {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.0.into_response_context()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for DieselError {
+    fn into_response(self) -> Response {
+        self.into_response_context().into_response()
+    }
+}
@@ -0,0 +1,203 @@
+use http::HeaderValue;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::ErrorFormat;
+
+/// Wire encoding of structured error bodies. The layout is chosen by
+/// [`ErrorFormat`]; the encoding only decides how it is serialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum BodyEncoding {
+    Json,
+    #[cfg(feature = "xml")]
+    Xml,
+    #[cfg(feature = "msgpack")]
+    MsgPack,
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl BodyEncoding {
+    const ALL: &'static [BodyEncoding] = &[
+        BodyEncoding::Json,
+        #[cfg(feature = "xml")]
+        BodyEncoding::Xml,
+        #[cfg(feature = "msgpack")]
+        BodyEncoding::MsgPack,
+        #[cfg(feature = "cbor")]
+        BodyEncoding::Cbor,
+    ];
+
+    /// Content type sent with a body of the given format in this encoding.
+    pub fn content_type(&self, format: ErrorFormat) -> HeaderValue {
+        match self {
+            BodyEncoding::Json => format.content_type(),
+            #[cfg(feature = "xml")]
+            BodyEncoding::Xml => match format {
+                ErrorFormat::Problem => HeaderValue::from_static("application/problem+xml"),
+                _ => HeaderValue::from_static("application/xml"),
+            },
+            #[cfg(feature = "msgpack")]
+            BodyEncoding::MsgPack => HeaderValue::from_static("application/msgpack"),
+            #[cfg(feature = "cbor")]
+            BodyEncoding::Cbor => HeaderValue::from_static("application/cbor"),
+        }
+    }
+
+    fn media_types(&self) -> &'static [&'static str] {
+        match self {
+            BodyEncoding::Json => &["application/json", "application/problem+json"],
+            #[cfg(feature = "xml")]
+            BodyEncoding::Xml => &["application/xml", "text/xml", "application/problem+xml"],
+            #[cfg(feature = "msgpack")]
+            BodyEncoding::MsgPack => &[
+                "application/msgpack",
+                "application/x-msgpack",
+                "application/vnd.msgpack",
+            ],
+            #[cfg(feature = "cbor")]
+            BodyEncoding::Cbor => &["application/cbor"],
+        }
+    }
+
+    /// Picks the encoding preferred by an `Accept` header, or `None` when the
+    /// client does not ask for any supported media type explicitly.
+    pub(crate) fn negotiate(accept: &str) -> Option<BodyEncoding> {
+        media_ranges(accept).iter().find_map(|media_type| {
+            Self::ALL.iter().copied().find(|encoding| {
+                encoding
+                    .media_types()
+                    .iter()
+                    .any(|supported| supported.eq_ignore_ascii_case(media_type))
+            })
+        })
+    }
+
+    #[cfg_attr(not(feature = "xml"), allow(unused_variables))]
+    pub(crate) fn encode(&self, body: &Value, format: ErrorFormat) -> Vec<u8> {
+        match self {
+            BodyEncoding::Json => serde_json::to_vec(body).unwrap_or_default(),
+            #[cfg(feature = "xml")]
+            BodyEncoding::Xml => xml::to_xml(body, format).into_bytes(),
+            #[cfg(feature = "msgpack")]
+            BodyEncoding::MsgPack => rmp_serde::to_vec_named(body).unwrap_or_default(),
+            #[cfg(feature = "cbor")]
+            BodyEncoding::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(body, &mut bytes).ok();
+                bytes
+            }
+        }
+    }
+}
+
+/// Media types listed in an `Accept` header, most preferred first. Ranges
+/// with `q=0` are dropped.
+pub(crate) fn media_ranges(accept: &str) -> Vec<&str> {
+    let mut ranges: Vec<(&str, f32)> = accept
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let media_type = parts.next()?.trim();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse().ok())
+                .unwrap_or(1.0);
+
+            Some((media_type, quality))
+        })
+        .filter(|(_, quality)| *quality > 0.0)
+        .collect();
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges
+        .into_iter()
+        .map(|(media_type, _)| media_type)
+        .collect()
+}
+
+#[cfg(feature = "xml")]
+mod xml {
+    use serde_json::Value;
+
+    use crate::ErrorFormat;
+
+    /// Writes the body as an XML document. Problem details follow the XML
+    /// format from RFC 9457 Appendix B, including `<i>` for array items.
+    pub(super) fn to_xml(body: &Value, format: ErrorFormat) -> String {
+        let mut out = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+
+        match format {
+            ErrorFormat::Problem => {
+                out.push_str(r#"<problem xmlns="urn:ietf:rfc:7807">"#);
+                write_children(&mut out, body);
+                out.push_str("</problem>");
+            }
+            _ => write_element(&mut out, "response", body),
+        }
+
+        out
+    }
+
+    fn write_children(out: &mut String, value: &Value) {
+        match value {
+            Value::Object(members) => {
+                for (name, value) in members {
+                    write_element(out, &element_name(name), value);
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    write_element(out, "i", item);
+                }
+            }
+            Value::Null => {}
+            Value::String(text) => escape(out, text),
+            other => escape(out, &other.to_string()),
+        }
+    }
+
+    fn write_element(out: &mut String, name: &str, value: &Value) {
+        if value.is_null() {
+            out.push_str(&format!("<{name}/>"));
+        } else {
+            out.push_str(&format!("<{name}>"));
+            write_children(out, value);
+            out.push_str(&format!("</{name}>"));
+        }
+    }
+
+    /// Replaces characters that are not allowed in XML element names.
+    fn element_name(name: &str) -> String {
+        let name: String = name
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        match name.chars().next() {
+            Some(c) if c.is_alphabetic() || c == '_' => name,
+            _ => format!("_{name}"),
+        }
+    }
+
+    fn escape(out: &mut String, text: &str) {
+        for c in text.chars() {
+            match c {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '"' => out.push_str("&quot;"),
+                '\'' => out.push_str("&apos;"),
+                c => out.push(c),
+            }
+        }
+    }
+}
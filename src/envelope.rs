@@ -0,0 +1,111 @@
+use std::sync::OnceLock;
+
+static ENVELOPE_CONFIG: OnceLock<EnvelopeConfig> = OnceLock::new();
+
+/// Sets the envelope layout used by every context that does not carry its own
+/// [`EnvelopeConfig`].
+///
+/// Can only be set once; later calls return the rejected config.
+pub fn set_envelope_config(config: EnvelopeConfig) -> Result<(), EnvelopeConfig> {
+    ENVELOPE_CONFIG.set(config)
+}
+
+/// Member names and shape of the default `{"result": null, "error": {...}}`
+/// envelope.
+///
+/// ```rust
+/// use axum_error_handler::EnvelopeConfig;
+///
+/// // {"data": null, "err": {"code": ..., "message": ...}}
+/// let config = EnvelopeConfig::new().result_member("data").error_member("err");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvelopeConfig {
+    result_member: &'static str,
+    error_member: &'static str,
+    code_member: &'static str,
+    message_member: &'static str,
+    omit_result: bool,
+    flat: bool,
+}
+
+impl EnvelopeConfig {
+    pub const fn new() -> Self {
+        Self {
+            result_member: "result",
+            error_member: "error",
+            code_member: "code",
+            message_member: "message",
+            omit_result: false,
+            flat: false,
+        }
+    }
+
+    /// The configuration set with [`set_envelope_config`], or the defaults.
+    pub fn global() -> Self {
+        ENVELOPE_CONFIG.get().copied().unwrap_or_default()
+    }
+
+    pub const fn result_member(mut self, name: &'static str) -> Self {
+        self.result_member = name;
+        self
+    }
+
+    pub const fn error_member(mut self, name: &'static str) -> Self {
+        self.error_member = name;
+        self
+    }
+
+    pub const fn code_member(mut self, name: &'static str) -> Self {
+        self.code_member = name;
+        self
+    }
+
+    pub const fn message_member(mut self, name: &'static str) -> Self {
+        self.message_member = name;
+        self
+    }
+
+    /// Leaves out the `"result": null` member, producing `{"error": {...}}`.
+    pub const fn omit_result(mut self) -> Self {
+        self.omit_result = true;
+        self
+    }
+
+    /// Renders the error members at the top level, producing
+    /// `{"code": ..., "message": ...}`.
+    pub const fn flat(mut self) -> Self {
+        self.flat = true;
+        self
+    }
+
+    pub fn result_member_name(&self) -> &'static str {
+        self.result_member
+    }
+
+    pub fn error_member_name(&self) -> &'static str {
+        self.error_member
+    }
+
+    pub fn code_member_name(&self) -> &'static str {
+        self.code_member
+    }
+
+    pub fn message_member_name(&self) -> &'static str {
+        self.message_member
+    }
+
+    pub fn is_result_omitted(&self) -> bool {
+        self.omit_result
+    }
+
+    pub fn is_flat(&self) -> bool {
+        self.flat
+    }
+}
+
+impl Default for EnvelopeConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
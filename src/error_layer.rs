@@ -0,0 +1,230 @@
+use std::{
+    convert::Infallible,
+    error::Error,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::response::{IntoResponse, Response};
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{
+    mapping::{fallback_context, map_chain},
+    ErrorResponseContext, IntoErrorResponseContext,
+};
+
+/// Errors as returned by fallible middleware such as timeouts and load
+/// shedding.
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Layer that renders the errors of fallible services, such as tower's
+/// timeout, load-shed and buffer middleware, in the crate's format. The
+/// service's error is converted into `E` and rendered through
+/// [`IntoErrorResponseContext`]; the wrapped service never fails, as axum
+/// requires.
+///
+/// With the default `E` of [`BoxError`], the error is mapped by the first
+/// mapper registered for an error in its `source()` chain, see
+/// [`register_error_type`](crate::register_error_type), then as one of
+/// tower's own errors with the `tower` feature, and by the fallback mapper
+/// otherwise.
+///
+/// ```rust,ignore
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .layer(
+///         ServiceBuilder::new()
+///             .layer(ErrorHandlerLayer::new())
+///             .timeout(Duration::from_secs(10)),
+///     );
+/// ```
+pub struct ErrorHandlerLayer<E = BoxError> {
+    _error: PhantomData<fn() -> E>,
+}
+
+impl ErrorHandlerLayer {
+    pub fn new() -> Self {
+        Self::with_error_type()
+    }
+}
+
+impl<E> ErrorHandlerLayer<E> {
+    /// A layer converting the service's errors into `E`, e.g. a derived
+    /// error enum with `From` impls for them:
+    /// `ErrorHandlerLayer::<GatewayError>::with_error_type()`.
+    pub fn with_error_type() -> Self {
+        Self {
+            _error: PhantomData,
+        }
+    }
+}
+
+impl Default for ErrorHandlerLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Clone for ErrorHandlerLayer<E> {
+    fn clone(&self) -> Self {
+        Self::with_error_type()
+    }
+}
+
+impl<E> fmt::Debug for ErrorHandlerLayer<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorHandlerLayer").finish()
+    }
+}
+
+impl<S, E> Layer<S> for ErrorHandlerLayer<E> {
+    type Service = ErrorHandlerService<S, E>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ErrorHandlerService {
+            inner,
+            ready_error: None,
+        }
+    }
+}
+
+/// Service produced by [`ErrorHandlerLayer`].
+pub struct ErrorHandlerService<S, E = BoxError> {
+    inner: S,
+    /// The error the inner service failed to become ready with, rendered as
+    /// the response to the next call.
+    ready_error: Option<E>,
+}
+
+impl<S: Clone, E> Clone for ErrorHandlerService<S, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            ready_error: None,
+        }
+    }
+}
+
+impl<S: fmt::Debug, E> fmt::Debug for ErrorHandlerService<S, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorHandlerService")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<S, E, R> Service<R> for ErrorHandlerService<S, E>
+where
+    S: Service<R, Response = Response>,
+    S::Error: Into<E>,
+    E: IntoErrorResponseContext,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = ErrorHandlerFuture<S::Future, E>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.ready_error.is_some() {
+            return Poll::Ready(Ok(()));
+        }
+
+        match self.inner.poll_ready(cx) {
+            Poll::Ready(Err(err)) => {
+                self.ready_error = Some(err.into());
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        match self.ready_error.take() {
+            Some(err) => ErrorHandlerFuture {
+                inner: None,
+                error: Some(err),
+            },
+            None => ErrorHandlerFuture {
+                inner: Some(self.inner.call(req)),
+                error: None,
+            },
+        }
+    }
+}
+
+pin_project! {
+    /// Response future of [`ErrorHandlerService`].
+    pub struct ErrorHandlerFuture<F, E> {
+        #[pin]
+        inner: Option<F>,
+        error: Option<E>,
+    }
+}
+
+impl<F, T, E> Future for ErrorHandlerFuture<F, E>
+where
+    F: Future<Output = Result<Response, T>>,
+    T: Into<E>,
+    E: IntoErrorResponseContext,
+{
+    type Output = Result<Response, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let err = match this.inner.as_pin_mut() {
+            Some(inner) => match inner.poll(cx) {
+                Poll::Ready(Ok(response)) => return Poll::Ready(Ok(response)),
+                Poll::Ready(Err(err)) => err.into(),
+                Poll::Pending => return Poll::Pending,
+            },
+            None => this
+                .error
+                .take()
+                .expect("ErrorHandlerFuture polled after completion"),
+        };
+
+        Poll::Ready(Ok(err.into_response_context().into_response()))
+    }
+}
+
+impl IntoErrorResponseContext for BoxError {
+    fn into_response_context(self) -> ErrorResponseContext {
+        let err: &(dyn Error + 'static) = &*self;
+        let chain = || std::iter::successors(Some(err), |&err| err.source());
+
+        map_chain(chain())
+            .or_else(|| chain().find_map(tower_context))
+            .unwrap_or_else(|| fallback_context(err))
+    }
+}
+
+/// Contexts for the errors of tower's own middleware.
+#[cfg(feature = "tower")]
+fn tower_context(err: &(dyn Error + 'static)) -> Option<ErrorResponseContext> {
+    use tower::{
+        buffer::error::{Closed, ServiceError},
+        load_shed::error::Overloaded,
+        timeout::error::Elapsed,
+    };
+
+    if err.is::<Elapsed>() {
+        Some(ErrorResponseContext::from_status(
+            http::StatusCode::REQUEST_TIMEOUT,
+        ))
+    } else if err.is::<Overloaded>() || err.is::<Closed>() || err.is::<ServiceError>() {
+        Some(ErrorResponseContext::service_unavailable())
+    } else {
+        None
+    }
+}
+
+#[cfg(not(feature = "tower"))]
+fn tower_context(_err: &(dyn Error + 'static)) -> Option<ErrorResponseContext> {
+    None
+}
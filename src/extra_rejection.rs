@@ -0,0 +1,85 @@
+//! Contexts for axum-extra's extractor rejections, coded like the ones of
+//! axum's own extractors.
+
+use axum::extract::rejection::RawFormRejection;
+use axum_extra::{
+    extract::{FormRejection, OptionalQueryRejection, QueryRejection},
+    typed_header::TypedHeaderRejection,
+};
+use http::StatusCode;
+
+use crate::{
+    rejection::{impl_into_error_response_context, rejection_context},
+    ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+/// A 400 naming the header in `details`, so clients can tell which one to fix
+/// without parsing the message.
+impl ToErrorResponseContext for TypedHeaderRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let code = match self.is_missing() {
+            true => "MISSING_HEADER",
+            false => "INVALID_HEADER",
+        };
+
+        rejection_context(self, StatusCode::BAD_REQUEST, code, self.to_string()).override_with(
+            |builder| builder.details(serde_json::json!({ "header": self.name().as_str() })),
+        )
+    }
+}
+
+impl ToErrorResponseContext for QueryRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        rejection_context(
+            self,
+            StatusCode::BAD_REQUEST,
+            "INVALID_QUERY_STRING",
+            format!("Failed to deserialize query string: {}", self),
+        )
+    }
+}
+
+impl ToErrorResponseContext for OptionalQueryRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        rejection_context(
+            self,
+            StatusCode::BAD_REQUEST,
+            "INVALID_QUERY_STRING",
+            format!("Failed to deserialize query string: {}", self),
+        )
+    }
+}
+
+impl ToErrorResponseContext for FormRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        match self {
+            FormRejection::RawFormRejection(RawFormRejection::InvalidFormContentType(inner)) => {
+                rejection_context(
+                    self,
+                    inner.status(),
+                    "MISSING_FORM_CONTENT_TYPE",
+                    inner.body_text(),
+                )
+            }
+            FormRejection::RawFormRejection(inner) => rejection_context(
+                self,
+                inner.status(),
+                "INVALID_REQUEST_BODY",
+                inner.body_text(),
+            ),
+            _ => rejection_context(
+                self,
+                StatusCode::BAD_REQUEST,
+                "INVALID_FORM_DATA",
+                format!("Failed to deserialize form: {}", self),
+            ),
+        }
+    }
+}
+
+impl_into_error_response_context!(
+    TypedHeaderRejection,
+    QueryRejection,
+    OptionalQueryRejection,
+    FormRejection
+);
@@ -0,0 +1,129 @@
+//! Responses for `eyre::Report`, found like those of
+//! [`AnyhowError`](crate::AnyhowError), with the client-facing message
+//! optionally carried by the report's handler.
+
+use std::{error::Error, fmt};
+
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+use eyre::{DefaultHandler, EyreHandler, InstallError};
+
+use crate::{
+    mapping::{fallback_context, map_chain},
+    ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+/// Wraps an [`eyre::Report`] so handlers can use `?` on any error. The
+/// response is the context of the first registered error in the chain and the
+/// fallback set with [`set_fallback_mapper`](crate::set_fallback_mapper)
+/// otherwise. A message attached with [`WithResponseMessage`] replaces the
+/// context's message.
+pub struct EyreReport(pub eyre::Report);
+
+impl<E: Into<eyre::Report>> From<E> for EyreReport {
+    fn from(err: E) -> Self {
+        Self(err.into())
+    }
+}
+
+impl fmt::Debug for EyreReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for EyreReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToErrorResponseContext for EyreReport {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let ctx = map_chain(self.0.chain()).unwrap_or_else(|| fallback_context(self.0.as_ref()));
+        let message = self
+            .0
+            .handler()
+            .downcast_ref::<ResponseHandler>()
+            .and_then(ResponseHandler::message);
+
+        match message {
+            Some(message) => ctx.override_with(|builder| builder.message(message)),
+            None => ctx,
+        }
+    }
+}
+
+impl IntoErrorResponseContext for EyreReport {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for EyreReport {
+    fn into_response(self) -> Response {
+        self.into_response_context().into_response()
+    }
+}
+
+/// An `eyre` handler that formats reports like the default one and carries
+/// the message sent to clients, set with [`WithResponseMessage`].
+pub struct ResponseHandler {
+    inner: Box<dyn EyreHandler>,
+    message: Option<String>,
+}
+
+impl ResponseHandler {
+    /// Installs the handler as `eyre`'s hook. Fails when another hook was
+    /// installed first.
+    pub fn install() -> Result<(), InstallError> {
+        eyre::set_hook(Box::new(|err| {
+            Box::new(ResponseHandler {
+                inner: DefaultHandler::default_with(err),
+                message: None,
+            })
+        }))
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl EyreHandler for ResponseHandler {
+    fn debug(&self, error: &(dyn Error + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.debug(error, f)
+    }
+
+    fn display(&self, error: &(dyn Error + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.display(error, f)
+    }
+
+    fn track_caller(&mut self, location: &'static std::panic::Location<'static>) {
+        self.inner.track_caller(location)
+    }
+}
+
+/// Attaches the message sent to clients to a report, e.g.
+/// `load().response_message("Could not load your account")?`. Has no effect
+/// unless [`ResponseHandler`] is installed.
+pub trait WithResponseMessage {
+    fn response_message(self, message: impl Into<String>) -> Self;
+}
+
+impl WithResponseMessage for eyre::Report {
+    fn response_message(mut self, message: impl Into<String>) -> Self {
+        if let Some(handler) = self.handler_mut().downcast_mut::<ResponseHandler>() {
+            handler.message = Some(message.into());
+        }
+
+        self
+    }
+}
+
+impl<T> WithResponseMessage for Result<T, eyre::Report> {
+    fn response_message(self, message: impl Into<String>) -> Self {
+        self.map_err(|report| report.response_message(message))
+    }
+}
@@ -0,0 +1,100 @@
+//! Fallbacks for requests that match no route or none of its methods, so they
+//! get an error body in the crate's format instead of axum's empty one.
+
+use std::{
+    convert::Infallible,
+    future::{ready, Ready},
+    task::{Context, Poll},
+};
+
+use axum::{
+    http::{Request, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tower_service::Service;
+
+use crate::{ErrorFormat, ErrorResponseBuilder, ErrorResponseContext};
+
+fn not_found_context(format: ErrorFormat) -> ErrorResponseContext {
+    ErrorResponseBuilder::new()
+        .status_code(StatusCode::NOT_FOUND)
+        .code("ROUTE_NOT_FOUND")
+        .message("Route not found")
+        .format(format)
+        .build()
+}
+
+/// Handler for [`Router::fallback`](axum::Router::fallback) answering with a
+/// 404 with code `ROUTE_NOT_FOUND`.
+///
+/// ```rust,ignore
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .fallback(not_found_handler);
+/// ```
+pub async fn not_found_handler() -> Response {
+    not_found_context(ErrorFormat::Envelope).into_response()
+}
+
+/// Service for [`Router::fallback_service`](axum::Router::fallback_service)
+/// answering like [`not_found_handler`], in the envelope or another
+/// [`ErrorFormat`].
+///
+/// ```rust,ignore
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .fallback_service(NotFoundService::new().format(ErrorFormat::Problem));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NotFoundService {
+    format: ErrorFormat,
+}
+
+impl NotFoundService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn format(mut self, format: ErrorFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+impl<B> Service<Request<B>> for NotFoundService {
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Ready<Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: Request<B>) -> Self::Future {
+        ready(Ok(not_found_context(self.format).into_response()))
+    }
+}
+
+/// Handler for [`MethodRouter::fallback`](axum::routing::MethodRouter::fallback)
+/// answering with a 405 with code `METHOD_NOT_ALLOWED`. axum still adds the
+/// `Allow` header listing the route's methods.
+///
+/// axum 0.7 has no router-wide `method_not_allowed_fallback`, so it is set on
+/// each route:
+///
+/// ```rust,ignore
+/// let app = Router::new().route(
+///     "/orders",
+///     get(list_orders)
+///         .post(create_order)
+///         .fallback(method_not_allowed_handler),
+/// );
+/// ```
+pub async fn method_not_allowed_handler() -> Response {
+    ErrorResponseBuilder::new()
+        .status_code(StatusCode::METHOD_NOT_ALLOWED)
+        .code("METHOD_NOT_ALLOWED")
+        .message("Method not allowed")
+        .build()
+        .into_response()
+}
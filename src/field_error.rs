@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// A problem with a single input field, reported in the `fields` member of
+/// the body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    field: String,
+    code: String,
+    message: String,
+}
+
+impl FieldError {
+    pub fn new(
+        field: impl Into<String>,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Path of the offending field, e.g. `email` or `items[0].quantity`.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
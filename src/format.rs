@@ -0,0 +1,359 @@
+use std::sync::OnceLock;
+
+use http::HeaderValue;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    config::{client_code, client_help_url, client_message, client_title, exposes_details},
+    current_request,
+    request_id::current_request_id,
+    EnvelopeConfig, ErrorConfig, ErrorResponseContext,
+};
+
+static PROBLEM_CONTENT_TYPE: OnceLock<HeaderValue> = OnceLock::new();
+static INCLUDE_CAUSES: OnceLock<bool> = OnceLock::new();
+static INCLUDE_TIMESTAMP: OnceLock<bool> = OnceLock::new();
+static INCLUDE_CHAIN: OnceLock<bool> = OnceLock::new();
+
+/// Overrides the content type sent with problem-details bodies for the whole
+/// application, e.g. plain `application/json` for clients that do not
+/// understand `application/problem+json`.
+///
+/// Can only be set once; later calls return the rejected value.
+pub fn set_problem_content_type(content_type: HeaderValue) -> Result<(), HeaderValue> {
+    PROBLEM_CONTENT_TYPE.set(content_type)
+}
+
+/// Adds a `causes` array with the messages of the error's `source()` chain to
+/// structured bodies. Meant for development builds, as the messages of
+/// underlying errors may reveal internals. Overrides
+/// [`ErrorConfig::expose_sources`]. Internal errors, and 5xx errors with
+/// [`ErrorConfig::expose_messages`] off, never render them.
+///
+/// Can only be set once; later calls return the rejected value.
+pub fn set_include_causes(include: bool) -> Result<(), bool> {
+    INCLUDE_CAUSES.set(include)
+}
+
+/// Adds a `timestamp` member with the RFC 3339 time the context was built to
+/// structured bodies, to correlate client reports with server logs.
+///
+/// Can only be set once; later calls return the rejected value.
+pub fn set_include_timestamp(include: bool) -> Result<(), bool> {
+    INCLUDE_TIMESTAMP.set(include)
+}
+
+/// Adds a `chain` array with the code and message of every level of a nested
+/// error, from the outermost variant to the leaf, to structured bodies. Meant
+/// for staging environments; production bodies only show the leaf.
+///
+/// Can only be set once; later calls return the rejected value.
+pub fn set_include_chain(include: bool) -> Result<(), bool> {
+    INCLUDE_CHAIN.set(include)
+}
+
+/// Body layout used when rendering an [`ErrorResponseContext`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ErrorFormat {
+    /// `{"result": null, "error": {"code": ..., "message": ...}}`
+    #[default]
+    Envelope,
+    /// RFC 9457 problem details: `type`, `title`, `status`, `detail`, `instance`.
+    Problem,
+    /// JSON:API `{"errors": [{status, code, title, detail, source}]}`.
+    #[cfg(feature = "jsonapi")]
+    JsonApi,
+    /// GraphQL `{"errors": [{"message", "extensions": {"code"}}], "data": null}`.
+    GraphQl,
+    /// `CODE: message` as `text/plain`, for health checks and CLI clients.
+    Text,
+    /// An HTML error page, rendered by the built-in template or the renderer
+    /// registered with [`set_html_renderer`](crate::set_html_renderer).
+    #[cfg(feature = "html")]
+    Html,
+}
+
+impl ErrorFormat {
+    /// Content type sent with this format unless the context overrides it.
+    pub fn content_type(&self) -> HeaderValue {
+        match self {
+            ErrorFormat::Problem => PROBLEM_CONTENT_TYPE
+                .get()
+                .cloned()
+                .unwrap_or_else(|| HeaderValue::from_static("application/problem+json")),
+            #[cfg(feature = "jsonapi")]
+            ErrorFormat::JsonApi => HeaderValue::from_static("application/vnd.api+json"),
+            _ => HeaderValue::from_static("application/json"),
+        }
+    }
+}
+
+pub(crate) fn text_body(ctx: &ErrorResponseContext) -> String {
+    format!("{}: {}", client_code(ctx), client_message(ctx))
+}
+
+/// The JSON value for formats that can be serialized by any
+/// [`BodyEncoding`](crate::BodyEncoding).
+pub(crate) fn structured_body(ctx: &ErrorResponseContext) -> Value {
+    match ctx.format() {
+        ErrorFormat::Problem => problem_body(ctx),
+        #[cfg(feature = "jsonapi")]
+        ErrorFormat::JsonApi => jsonapi_body(ctx),
+        ErrorFormat::GraphQl => graphql_body(ctx),
+        _ => envelope_body(ctx),
+    }
+}
+
+fn envelope_body(ctx: &ErrorResponseContext) -> Value {
+    let config = ctx.envelope().unwrap_or_else(EnvelopeConfig::global);
+
+    let mut error = Map::new();
+    error.insert(
+        config.code_member_name().to_string(),
+        client_code(ctx).into(),
+    );
+    error.insert(
+        config.message_member_name().to_string(),
+        client_message(ctx).into(),
+    );
+
+    if let Some(help_url) = client_help_url(ctx) {
+        error.insert("help_url".to_string(), help_url.into());
+    }
+
+    if let Some(secs) = ctx.retry_after_secs() {
+        error.insert("retry_after".to_string(), secs.into());
+    }
+
+    insert_extension_members(&mut error, ctx);
+
+    if let Some(details) = exposed_details(ctx) {
+        error.insert("details".to_string(), details.clone());
+    }
+
+    insert_optional_members(&mut error, ctx);
+
+    if config.is_flat() {
+        return Value::Object(error);
+    }
+
+    let mut body = Map::new();
+
+    if !config.is_result_omitted() {
+        body.insert(config.result_member_name().to_string(), Value::Null);
+    }
+
+    body.insert(config.error_member_name().to_string(), Value::Object(error));
+
+    Value::Object(body)
+}
+
+fn problem_body(ctx: &ErrorResponseContext) -> Value {
+    let status = ctx.status_code();
+    let title = client_title(ctx)
+        .or_else(|| status.canonical_reason())
+        .unwrap_or_default();
+    // The type of an internal error is usually derived from its code.
+    let type_uri = ctx.type_uri().filter(|_| !ctx.is_internal());
+
+    let mut problem = Map::new();
+    problem.insert("type".to_string(), type_uri.unwrap_or("about:blank").into());
+    problem.insert("title".to_string(), title.into());
+    problem.insert("status".to_string(), status.as_u16().into());
+    problem.insert("detail".to_string(), client_message(ctx).into());
+
+    match ctx.instance() {
+        Some(instance) => {
+            problem.insert("instance".to_string(), instance.into());
+        }
+        None => {
+            if let Some(request) = current_request() {
+                problem.insert("instance".to_string(), request.uri().path().into());
+            }
+        }
+    }
+
+    problem.insert("code".to_string(), client_code(ctx).into());
+
+    if let Some(help_url) = client_help_url(ctx) {
+        problem.insert("help_url".to_string(), help_url.into());
+    }
+
+    if let Some(secs) = ctx.retry_after_secs() {
+        problem.insert("retry_after".to_string(), secs.into());
+    }
+
+    insert_extension_members(&mut problem, ctx);
+    insert_optional_members(&mut problem, ctx);
+
+    match exposed_details(ctx) {
+        Some(Value::Object(details)) => {
+            for (name, value) in details {
+                problem.entry(name.clone()).or_insert_with(|| value.clone());
+            }
+        }
+        Some(details) => {
+            problem
+                .entry("details".to_string())
+                .or_insert_with(|| details.clone());
+        }
+        None => {}
+    }
+
+    Value::Object(problem)
+}
+
+/// A GraphQL error result. The code, extension members and details are placed
+/// under the error's `extensions`.
+fn graphql_body(ctx: &ErrorResponseContext) -> Value {
+    let mut extensions = Map::new();
+    extensions.insert("code".to_string(), client_code(ctx).into());
+
+    if let Some(help_url) = client_help_url(ctx) {
+        extensions.insert("help_url".to_string(), help_url.into());
+    }
+
+    if let Some(secs) = ctx.retry_after_secs() {
+        extensions.insert("retry_after".to_string(), secs.into());
+    }
+
+    insert_extension_members(&mut extensions, ctx);
+
+    if let Some(details) = exposed_details(ctx) {
+        extensions.insert("details".to_string(), details.clone());
+    }
+
+    insert_optional_members(&mut extensions, ctx);
+
+    serde_json::json!({
+        "errors": [{
+            "message": client_message(ctx),
+            "extensions": extensions,
+        }],
+        "data": null,
+    })
+}
+
+/// A single JSON:API error object. An extension member named `source` is used
+/// as the error's `source`; the remaining members and the details go under
+/// `meta`. The help URL becomes the `about` link.
+#[cfg(feature = "jsonapi")]
+fn jsonapi_body(ctx: &ErrorResponseContext) -> Value {
+    let status = ctx.status_code();
+
+    let mut error = Map::new();
+    error.insert("status".to_string(), status.as_str().into());
+    error.insert("code".to_string(), client_code(ctx).into());
+
+    if let Some(title) = client_title(ctx).or_else(|| status.canonical_reason()) {
+        error.insert("title".to_string(), title.into());
+    }
+
+    error.insert("detail".to_string(), client_message(ctx).into());
+
+    if let Some(help_url) = client_help_url(ctx) {
+        error.insert(
+            "links".to_string(),
+            serde_json::json!({ "about": help_url }),
+        );
+    }
+
+    let mut meta = Map::new();
+    insert_extension_members(&mut meta, ctx);
+
+    if let Some(source) = meta.remove("source") {
+        error.insert("source".to_string(), source);
+    }
+
+    if let Some(secs) = ctx.retry_after_secs() {
+        meta.insert("retry_after".to_string(), secs.into());
+    }
+
+    if let Some(details) = exposed_details(ctx) {
+        meta.insert("details".to_string(), details.clone());
+    }
+
+    insert_optional_members(&mut meta, ctx);
+
+    if !meta.is_empty() {
+        error.insert("meta".to_string(), Value::Object(meta));
+    }
+
+    serde_json::json!({ "errors": [error] })
+}
+
+/// The context's details, unless they are withheld, see [`exposes_details`].
+fn exposed_details(ctx: &ErrorResponseContext) -> Option<&Value> {
+    ctx.details().filter(|_| exposes_details(ctx))
+}
+
+fn insert_extension_members(object: &mut Map<String, Value>, ctx: &ErrorResponseContext) {
+    if !exposes_details(ctx) {
+        return;
+    }
+
+    for (name, value) in ctx.extension_members() {
+        object.entry(name.clone()).or_insert_with(|| value.clone());
+    }
+}
+
+/// Inserts the members of [`insert_variant_members`], except for internal
+/// errors. Then inserts the trace id and the request id, when known. Last come
+/// the members enabled by [`set_include_timestamp`], [`set_include_causes`]
+/// and [`set_include_chain`]; the causes and the chain are left out where
+/// [`exposes_details`] is false, as they carry the messages of the underlying
+/// errors.
+fn insert_optional_members(object: &mut Map<String, Value>, ctx: &ErrorResponseContext) {
+    if !ctx.is_internal() {
+        insert_variant_members(object, ctx);
+    }
+
+    if let Some(trace_id) = ctx.trace_id() {
+        object.insert("trace_id".to_string(), trace_id.into());
+    }
+
+    if let Some(request_id) = current_request_id() {
+        if let Some(request_id) = request_id.as_str() {
+            object.insert("request_id".to_string(), request_id.into());
+        }
+    }
+
+    if INCLUDE_TIMESTAMP.get().copied().unwrap_or(false) {
+        let timestamp = humantime::format_rfc3339_millis(ctx.timestamp());
+        object.insert("timestamp".to_string(), timestamp.to_string().into());
+    }
+
+    let include_causes = INCLUDE_CAUSES
+        .get()
+        .copied()
+        .unwrap_or_else(|| ErrorConfig::global().expose_sources);
+    if include_causes && exposes_details(ctx) && !ctx.sources().is_empty() {
+        object.insert("causes".to_string(), ctx.sources().into());
+    }
+
+    let include_chain = INCLUDE_CHAIN.get().copied().unwrap_or(false) && exposes_details(ctx);
+    if include_chain && !ctx.chain().is_empty() {
+        let chain = serde_json::to_value(ctx.chain()).unwrap_or_default();
+        object.insert("chain".to_string(), chain);
+    }
+}
+
+/// Inserts the field errors and the message key and arguments, which describe
+/// the variant rather than the response.
+fn insert_variant_members(object: &mut Map<String, Value>, ctx: &ErrorResponseContext) {
+    if !ctx.field_errors().is_empty() {
+        let fields = serde_json::to_value(ctx.field_errors()).unwrap_or_default();
+        object.insert("fields".to_string(), fields);
+    }
+
+    if let Some(message_key) = ctx.message_key() {
+        object.insert("message_key".to_string(), message_key.into());
+    }
+
+    if let Some(message_args) = ctx.message_args() {
+        object.insert("message_args".to_string(), message_args.clone().into());
+    }
+}
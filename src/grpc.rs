@@ -0,0 +1,54 @@
+use http::StatusCode;
+use tonic::{metadata::MetadataMap, Code, Status};
+
+use crate::{
+    config::{client_code, client_message},
+    ErrorResponseContext,
+};
+
+/// Maps an HTTP status to the closest gRPC status code.
+pub fn grpc_code(status: StatusCode) -> Code {
+    match status.as_u16() {
+        400 | 422 => Code::InvalidArgument,
+        401 => Code::Unauthenticated,
+        403 => Code::PermissionDenied,
+        404 => Code::NotFound,
+        409 => Code::AlreadyExists,
+        412 => Code::FailedPrecondition,
+        413 => Code::OutOfRange,
+        429 => Code::ResourceExhausted,
+        499 => Code::Cancelled,
+        501 => Code::Unimplemented,
+        502 | 503 => Code::Unavailable,
+        504 => Code::DeadlineExceeded,
+        _ if status.is_success() => Code::Ok,
+        _ if status.is_client_error() => Code::FailedPrecondition,
+        _ if status.is_server_error() => Code::Internal,
+        _ => Code::Unknown,
+    }
+}
+
+/// Builds a gRPC status from an error context. The message becomes the status
+/// message, `{"code", "message"}` is attached as JSON in the details, and the
+/// context's headers are sent as metadata.
+pub fn to_grpc_status(ctx: &ErrorResponseContext) -> Status {
+    let details = serde_json::json!({
+        "code": client_code(ctx),
+        "message": client_message(ctx),
+    });
+
+    Status::with_details_and_metadata(
+        grpc_code(ctx.status_code()),
+        client_message(ctx),
+        serde_json::to_vec(&details).unwrap_or_default().into(),
+        MetadataMap::from_headers(ctx.headers().clone()),
+    )
+}
+
+/// Lets code shared between axum and tonic endpoints return contexts from
+/// both; see [`to_grpc_status`].
+impl From<ErrorResponseContext> for Status {
+    fn from(ctx: ErrorResponseContext) -> Self {
+        to_grpc_status(&ctx)
+    }
+}
@@ -0,0 +1,142 @@
+use std::{borrow::Cow, sync::OnceLock};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    config::{client_code, client_help_url, client_message, client_title, exposes_details},
+    encoding::media_ranges,
+    ErrorResponseContext,
+};
+
+/// Renders the HTML page for an error context.
+pub type HtmlRenderer = fn(&ErrorResponseContext) -> String;
+
+static HTML_RENDERER: OnceLock<HtmlRenderer> = OnceLock::new();
+static NO_EXTENSIONS: OnceLock<Map<String, Value>> = OnceLock::new();
+
+/// Replaces the built-in error page template for the whole application.
+///
+/// Can only be set once; later calls return the rejected renderer.
+pub fn set_html_renderer(renderer: HtmlRenderer) -> Result<(), HtmlRenderer> {
+    HTML_RENDERER.set(renderer)
+}
+
+/// What an error page shows, for templates rendered by a template engine such
+/// as askama or minijinja from a [`set_html_renderer`] renderer:
+///
+/// ```rust,ignore
+/// fn render(ctx: &ErrorResponseContext) -> String {
+///     TEMPLATES
+///         .get_template("error.html")
+///         .and_then(|template| template.render(HtmlPage::new(ctx)))
+///         .unwrap_or_else(|_| ctx.message().to_string())
+/// }
+/// ```
+///
+/// The values are not escaped; templates are expected to escape them.
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct HtmlPage<'a> {
+    pub status: u16,
+    /// The context's title, or the status's canonical reason.
+    pub title: &'a str,
+    pub code: &'a str,
+    pub message: Cow<'a, str>,
+    pub help_url: Option<&'a str>,
+    /// The context's extension members, unless they are withheld like in the
+    /// JSON bodies.
+    pub extensions: &'a Map<String, Value>,
+}
+
+impl<'a> HtmlPage<'a> {
+    pub fn new(ctx: &'a ErrorResponseContext) -> Self {
+        let status = ctx.status_code();
+
+        Self {
+            status: status.as_u16(),
+            title: client_title(ctx)
+                .or_else(|| status.canonical_reason())
+                .unwrap_or("Error"),
+            code: client_code(ctx),
+            message: client_message(ctx),
+            help_url: client_help_url(ctx),
+            extensions: match exposes_details(ctx) {
+                true => ctx.extension_members(),
+                false => NO_EXTENSIONS.get_or_init(Map::new),
+            },
+        }
+    }
+}
+
+/// Whether the client ranks `text/html` above every structured media type,
+/// as browsers do for page navigations.
+pub(crate) fn prefers_html(accept: &str) -> bool {
+    media_ranges(accept)
+        .first()
+        .is_some_and(|media_type| media_type.eq_ignore_ascii_case("text/html"))
+}
+
+pub(crate) fn render(ctx: &ErrorResponseContext) -> String {
+    match HTML_RENDERER.get() {
+        Some(renderer) => renderer(ctx),
+        None => default_page(ctx),
+    }
+}
+
+fn default_page(ctx: &ErrorResponseContext) -> String {
+    let page = HtmlPage::new(ctx);
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{status} {title}</title>
+<style>
+body {{ font-family: system-ui, sans-serif; background: #f6f7f9; color: #1f2328; margin: 0; }}
+main {{ max-width: 40rem; margin: 15vh auto; padding: 2rem; background: #fff; border-radius: 8px; box-shadow: 0 1px 3px rgba(0, 0, 0, .12); }}
+h1 {{ margin-top: 0; font-size: 1.5rem; }}
+.status {{ color: #cf222e; font-weight: 600; }}
+code {{ color: #57606a; }}
+</style>
+</head>
+<body>
+<main>
+<p class="status">{status}</p>
+<h1>{title}</h1>
+<p>{message}</p>
+<p><code>{code}</code></p>{help}
+</main>
+</body>
+</html>
+"#,
+        status = page.status,
+        title = escape(page.title),
+        message = escape(&page.message),
+        code = escape(page.code),
+        help = page
+            .help_url
+            .map(|url| format!("\n<p><a href=\"{}\">How to fix this</a></p>", escape(url)))
+            .unwrap_or_default(),
+    )
+}
+
+/// Escapes text for use in HTML element content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
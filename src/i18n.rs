@@ -0,0 +1,149 @@
+//! Localized messages, resolved by `message_key` in the language the client
+//! asks for with `Accept-Language`.
+
+use std::sync::OnceLock;
+
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use fluent_langneg::{accepted_languages, negotiate_languages, NegotiationStrategy};
+use serde_json::{Map, Value};
+use unic_langid::LanguageIdentifier;
+
+use crate::{current_request, ErrorResponseContext};
+
+static MESSAGE_RESOLVER: OnceLock<Box<dyn MessageResolver>> = OnceLock::new();
+
+/// Sets how messages are localized for the whole application.
+///
+/// Can only be set once; later calls return the rejected resolver.
+pub fn set_message_resolver(
+    resolver: impl MessageResolver,
+) -> Result<(), Box<dyn MessageResolver>> {
+    MESSAGE_RESOLVER.set(Box::new(resolver))
+}
+
+/// Looks up the message rendered for a context with a `message_key`, in place
+/// of the message it was built with.
+pub trait MessageResolver: Send + Sync + 'static {
+    /// The message for `key` with the context's `message_args`, in the first
+    /// language of `accept_language`, the request's `Accept-Language` header,
+    /// that has one. `None` keeps the context's own message.
+    fn resolve(
+        &self,
+        key: &str,
+        args: Option<&Map<String, Value>>,
+        accept_language: Option<&str>,
+    ) -> Option<String>;
+}
+
+/// Resolves messages from Fluent bundles, one per language, falling back to
+/// the bundle of the default language.
+///
+/// ```rust,ignore
+/// let mut en = FluentBundle::new_concurrent(vec![langid!("en-US")]);
+/// en.add_resource(FluentResource::try_new(include_str!("en-US.ftl").into())?)?;
+/// let mut de = FluentBundle::new_concurrent(vec![langid!("de")]);
+/// de.add_resource(FluentResource::try_new(include_str!("de.ftl").into())?)?;
+///
+/// axum_error_handler::set_message_resolver(
+///     FluentResolver::new(langid!("en-US")).bundle(en).bundle(de),
+/// )
+/// .ok();
+/// ```
+pub struct FluentResolver {
+    default_language: LanguageIdentifier,
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl FluentResolver {
+    pub fn new(default_language: LanguageIdentifier) -> Self {
+        Self {
+            default_language,
+            bundles: Vec::new(),
+        }
+    }
+
+    /// Adds the bundle of the first of its locales. Unicode isolation marks
+    /// are turned off, as messages are not rendered into bidirectional text.
+    pub fn bundle(mut self, mut bundle: FluentBundle<FluentResource>) -> Self {
+        bundle.set_use_isolating(false);
+        self.bundles.push(bundle);
+        self
+    }
+
+    fn language(bundle: &FluentBundle<FluentResource>) -> Option<&LanguageIdentifier> {
+        bundle.locales.first()
+    }
+}
+
+impl MessageResolver for FluentResolver {
+    fn resolve(
+        &self,
+        key: &str,
+        args: Option<&Map<String, Value>>,
+        accept_language: Option<&str>,
+    ) -> Option<String> {
+        let requested = accept_language
+            .map(accepted_languages::parse)
+            .unwrap_or_default();
+        let available = self
+            .bundles
+            .iter()
+            .filter_map(Self::language)
+            .collect::<Vec<_>>();
+        let default_language = &self.default_language;
+        let languages = negotiate_languages(
+            &requested,
+            &available,
+            Some(&default_language),
+            NegotiationStrategy::Filtering,
+        );
+        let args = args.map(fluent_args);
+
+        languages.into_iter().find_map(|language| {
+            let bundle = self
+                .bundles
+                .iter()
+                .find(|bundle| Self::language(bundle) == Some(*language))?;
+            let pattern = bundle.get_message(key)?.value()?;
+            let mut errors = Vec::new();
+
+            Some(
+                bundle
+                    .format_pattern(pattern, args.as_ref(), &mut errors)
+                    .into_owned(),
+            )
+        })
+    }
+}
+
+/// Message arguments as Fluent values: numbers stay numbers so plural rules
+/// apply, everything else is formatted as a string.
+fn fluent_args(args: &Map<String, Value>) -> FluentArgs<'_> {
+    args.iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(value) => FluentValue::from(value.as_str()),
+                Value::Number(number) => match number.as_f64() {
+                    Some(number) => FluentValue::from(number),
+                    None => FluentValue::from(number.to_string()),
+                },
+                value => FluentValue::from(value.to_string()),
+            };
+            (name.as_str(), value)
+        })
+        .collect()
+}
+
+/// The context's message resolved by the registered resolver, for contexts
+/// with a message key.
+pub(crate) fn resolve_message(ctx: &ErrorResponseContext) -> Option<String> {
+    let resolver = MESSAGE_RESOLVER.get()?;
+    let key = ctx.message_key()?;
+    let request = current_request();
+    let accept_language = request
+        .as_ref()
+        .and_then(|request| request.headers().get(http::header::ACCEPT_LANGUAGE))
+        .and_then(|value| value.to_str().ok());
+
+    resolver.resolve(key, ctx.message_args(), accept_language)
+}
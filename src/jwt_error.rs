@@ -0,0 +1,54 @@
+//! Responses for `jsonwebtoken` errors. Every failure is a 401; expired and
+//! tampered tokens get their own codes so clients know whether refreshing
+//! the token can help.
+
+use std::sync::OnceLock;
+
+use http::{header, HeaderValue, StatusCode};
+use jsonwebtoken::errors::{Error as JwtError, ErrorKind};
+
+use crate::{
+    sources::{ErrorSources, SourceChain},
+    ErrorResponseBuilder, ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+static WWW_AUTHENTICATE: OnceLock<HeaderValue> = OnceLock::new();
+
+/// Sends `challenge` as the `WWW-Authenticate` header of the responses for
+/// `jsonwebtoken` errors, e.g. `Bearer realm="api", error="invalid_token"`.
+///
+/// Can only be set once; later calls return the rejected value.
+pub fn set_www_authenticate(challenge: HeaderValue) -> Result<(), HeaderValue> {
+    WWW_AUTHENTICATE.set(challenge)
+}
+
+impl ToErrorResponseContext for JwtError {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let (code, message) = match self.kind() {
+            ErrorKind::ExpiredSignature => ("TOKEN_EXPIRED", "Token has expired"),
+            ErrorKind::InvalidSignature => ("TOKEN_INVALID", "Token is invalid"),
+            _ => ("UNAUTHORIZED", "Unauthorized"),
+        };
+
+        let mut sources = vec![self.to_string()];
+        sources.extend(SourceChain(self).sources());
+
+        let builder = ErrorResponseBuilder::new()
+            .status_code(StatusCode::UNAUTHORIZED)
+            .code(code)
+            .message(message)
+            .sources(sources);
+
+        match WWW_AUTHENTICATE.get() {
+            Some(challenge) => builder.header(header::WWW_AUTHENTICATE, challenge.clone()),
+            None => builder,
+        }
+        .build()
+    }
+}
+
+impl IntoErrorResponseContext for JwtError {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
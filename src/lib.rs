@@ -1,122 +1,140 @@
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse::ParseStream, parse_macro_input, Attribute, DeriveInput, Fields, LitStr};
-
-#[proc_macro_derive(AxumErrorResponse, attributes(status_code, code))]
-pub fn derive_axum_error_response(input: TokenStream) -> TokenStream {
-    // Parse the input tokens into a syntax tree
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
-
-    let variants = if let syn::Data::Enum(data_enum) = input.data {
-        data_enum.variants
-    } else {
-        panic!("AxumErrorResponse can only be derived for enums");
-    };
-
-    let match_arms = variants.iter().map(|variant| {
-        let ident = &variant.ident;
-
-        let status_code = variant
-            .attrs
-            .iter()
-            .find_map(|attr| parse_status_code(attr))
-            .unwrap_or(quote! { axum::http::StatusCode::INTERNAL_SERVER_ERROR });
-        let code = variant
-            .attrs
-            .iter()
-            .find_map(|attr| parse_code_string(attr))
-            .unwrap_or_else(|| ident.to_string());
-
-        let pattern = match &variant.fields {
-            Fields::Unit => quote! { #name::#ident },
-            Fields::Named(_) => {
-                quote! { #name::#ident { .. } }
-            }
-            Fields::Unnamed(_) => {
-                quote! { #name::#ident(..) }
-            }
-        };
-
-        let body = match &variant.fields {
-            Fields::Unit => quote! { format!("{}", self) },
-            Fields::Named(_) => {
-                quote! { self.to_string() }
-            }
-            Fields::Unnamed(_) => {
-                quote! { self.to_string() }
-            }
-        };
-
-        quote! {
-            #pattern => {
-                let body = #body;
-                let json = axum::Json(serde_json::json!({
-                    "result": null,
-                    "error": {
-                        "code": #code,
-                        "message": body,
-                    }
-                }));
-
-                axum::http::Response::builder()
-                    .status(#status_code)
-                    .header("content-type", "application/json")
-                    .body(json.into_response().into_body())
-                    .unwrap()
-            }
-        }
-    });
-
-    // Generate the final impl block
-    let expanded = quote! {
-        impl axum::response::IntoResponse for #name {
-            fn into_response(self) -> axum::response::Response {
-                match self {
-                    #(#match_arms),*
-                }
-            }
-        }
-    };
-
-    TokenStream::from(expanded)
-}
-
-fn parse_status_code(attr: &Attribute) -> Option<proc_macro2::TokenStream> {
-    if attr.path().is_ident("status_code") {
-        let result = attr.parse_args_with(|input: ParseStream| {
-            let fmt: LitStr = input.parse()?;
-
-            let val = fmt.value();
-
-            Ok(quote! { axum::http::StatusCode::from_u16(#val.parse().unwrap()).unwrap() })
-        });
-
-        if result.is_err() {
-            println!("Error parsing status code");
-            return Some(quote! { axum::http::StatusCode::INTERNAL_SERVER_ERROR });
-        }
-
-        Some(result.unwrap())
-    } else {
-        None
-    }
-}
-
-fn parse_code_string(attr: &Attribute) -> Option<String> {
-    if attr.path().is_ident("code") {
-        let result = attr.parse_args_with(|input: ParseStream| {
-            let fmt: LitStr = input.parse().unwrap();
-
-            Ok(fmt.value())
-        });
-
-        if result.is_err() {
-            return Some("".to_string());
-        }
-
-        Some(result.unwrap())
-    } else {
-        None
-    }
-}
+//! Structured error responses for axum.
+//!
+//! Derive [`AxumErrorResponse`] on an error enum to turn each variant into an
+//! [`ErrorResponseContext`], which renders as the JSON error body.
+//!
+//! The contexts and their body formats only depend on the `http` types. The
+//! axum glue, `IntoResponse` impls and the layers, is behind the default
+//! `axum` feature; without it, contexts render with
+//! [`ErrorResponseContext::into_http_response`].
+
+#[doc(hidden)]
+#[path = "private.rs"]
+pub mod __private;
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "anyhow")]
+mod anyhow_error;
+mod catalog;
+mod chain;
+mod close;
+mod config;
+mod context;
+#[cfg(feature = "diesel")]
+mod diesel_error;
+mod encoding;
+mod envelope;
+#[cfg(feature = "axum")]
+mod error_layer;
+#[cfg(feature = "axum-extra")]
+mod extra_rejection;
+#[cfg(feature = "eyre")]
+mod eyre_report;
+#[cfg(feature = "axum")]
+mod fallback;
+mod field_error;
+mod format;
+#[cfg(feature = "tonic")]
+pub mod grpc;
+#[cfg(feature = "html")]
+mod html;
+#[cfg(feature = "i18n")]
+mod i18n;
+#[cfg(feature = "jsonwebtoken")]
+mod jwt_error;
+#[cfg(feature = "tracing")]
+mod logging;
+mod mapping;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "multipart")]
+mod multipart_error;
+#[cfg(feature = "utoipa")]
+mod openapi;
+#[cfg(feature = "axum")]
+mod panic;
+#[cfg(feature = "axum")]
+mod parse;
+#[cfg(feature = "redis")]
+mod redis_error;
+#[cfg(feature = "rejections")]
+mod rejection;
+#[cfg(feature = "axum")]
+mod renderer;
+mod request;
+mod request_id;
+#[cfg(feature = "axum")]
+mod responder;
+mod schema;
+#[cfg(feature = "sea-orm")]
+mod sea_orm_error;
+#[cfg(feature = "sentry")]
+mod sentry;
+mod severity;
+mod sources;
+#[cfg(feature = "sqlx")]
+mod sqlx_error;
+#[cfg(feature = "axum")]
+mod sse;
+mod typescript;
+#[cfg(feature = "validator")]
+mod validation;
+
+#[cfg(feature = "anyhow")]
+pub use anyhow_error::AnyhowError;
+pub use axum_error_handler_macros::AxumErrorResponse;
+pub use catalog::{catalog, duplicate_codes, CatalogEntry, ErrorCatalog};
+pub use chain::ChainEntry;
+pub use config::{set_error_config, ErrorConfig};
+pub use context::{
+    ErrorResponseBuilder, ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+#[cfg(feature = "diesel")]
+pub use diesel_error::DieselError;
+pub use encoding::BodyEncoding;
+pub use envelope::{set_envelope_config, EnvelopeConfig};
+#[cfg(feature = "axum")]
+pub use error_layer::{BoxError, ErrorHandlerFuture, ErrorHandlerLayer, ErrorHandlerService};
+#[cfg(feature = "eyre")]
+pub use eyre_report::{EyreReport, ResponseHandler, WithResponseMessage};
+#[cfg(feature = "axum")]
+pub use fallback::{method_not_allowed_handler, not_found_handler, NotFoundService};
+pub use field_error::FieldError;
+#[cfg(feature = "i18n")]
+pub use fluent_bundle;
+pub use format::{
+    set_include_causes, set_include_chain, set_include_timestamp, set_problem_content_type,
+    ErrorFormat,
+};
+#[cfg(feature = "html")]
+pub use html::{escape as escape_html, set_html_renderer, HtmlPage, HtmlRenderer};
+#[cfg(feature = "i18n")]
+pub use i18n::{set_message_resolver, FluentResolver, MessageResolver};
+#[cfg(feature = "jsonwebtoken")]
+pub use jwt_error::set_www_authenticate;
+#[cfg(feature = "tracing")]
+pub use logging::{flush_log_sampling, set_log_sampling, LogSampling};
+pub use mapping::{register_error_mapper, register_error_type, set_fallback_mapper, ErrorMapper};
+#[cfg(feature = "utoipa")]
+pub use openapi::{error_responses, error_schema};
+#[cfg(feature = "axum")]
+pub use panic::{CatchPanicFuture, CatchPanicLayer, CatchPanicService};
+#[cfg(feature = "axum")]
+pub use parse::ParseResponseError;
+#[cfg(feature = "axum")]
+pub use renderer::{set_default_renderer, set_response_hook, DefaultRenderer, ResponseHook};
+pub use request::{current_request, RequestContextLayer, RequestContextService, RequestInfo};
+pub use request_id::{RequestId, RequestIdFuture, RequestIdLayer, RequestIdService};
+#[cfg(feature = "axum")]
+pub use responder::CustomErrorResponder;
+pub use schema::{envelope_json_schema, problem_json_schema};
+pub use severity::Severity;
+#[cfg(feature = "sqlx")]
+pub use sqlx_error::SqlxError;
+#[cfg(feature = "tonic")]
+pub use tonic;
+pub use typescript::typescript_definitions;
+#[cfg(feature = "utoipa")]
+pub use utoipa;
+#[cfg(feature = "validator")]
+pub use validation::ValidationRejection;
@@ -0,0 +1,221 @@
+//! Events emitted when derived errors are converted into responses.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, OnceLock, PoisonError},
+    time::{Duration, Instant},
+};
+
+use tracing::{dispatcher, Dispatch, Level};
+
+use crate::{ErrorResponseContext, Severity};
+
+static LOG_SAMPLING: OnceLock<LogSampling> = OnceLock::new();
+static WINDOWS: OnceLock<Mutex<Windows>> = OnceLock::new();
+
+/// Limits how many `error response` events with the same code and message
+/// are emitted per window, so a flood of identical errors, e.g. 400s from a
+/// single misbehaving client, doesn't drown the logs.
+///
+/// Can only be set once; later calls return the rejected value.
+pub fn set_log_sampling(sampling: LogSampling) -> Result<(), LogSampling> {
+    LOG_SAMPLING.set(sampling)
+}
+
+/// Closes the windows that have ended and reports what they dropped, for
+/// applications that want the report of a burst followed by silence without
+/// waiting for the next event, e.g. from a periodic task:
+///
+/// ```rust,ignore
+/// tokio::spawn(async {
+///     let mut interval = tokio::time::interval(Duration::from_secs(60));
+///     loop {
+///         interval.tick().await;
+///         axum_error_handler::flush_log_sampling();
+///     }
+/// });
+/// ```
+///
+/// Does nothing unless [`set_log_sampling`] was called.
+pub fn flush_log_sampling() {
+    let Some(sampling) = LOG_SAMPLING.get() else {
+        return;
+    };
+
+    let ended = close_ended(&mut lock_windows(), sampling, Instant::now());
+    for suppressed in ended {
+        emit_suppressed(suppressed);
+    }
+}
+
+/// At most `max_events` events per code and message in each `window`. Once a
+/// window has ended, an `error responses suppressed` event reports how many
+/// were dropped to the subscriber that was current when the window started.
+/// Ended windows are closed by the first event after them, at most once per
+/// `window`, or by [`flush_log_sampling`].
+///
+/// ```rust,ignore
+/// axum_error_handler::set_log_sampling(LogSampling::per_minute(10)).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogSampling {
+    pub max_events: u32,
+    pub window: Duration,
+}
+
+impl LogSampling {
+    pub const fn per_minute(max_events: u32) -> Self {
+        Self {
+            max_events,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+/// The open windows, by code and message.
+#[derive(Default)]
+struct Windows {
+    open: HashMap<(String, String), Window>,
+    /// When ended windows are next closed; `None` until the first event.
+    next_sweep: Option<Instant>,
+}
+
+/// The events of one code and message in the current window.
+struct Window {
+    started: Instant,
+    logged: u32,
+    suppressed: u64,
+    level: Severity,
+    dispatch: Dispatch,
+}
+
+/// Events dropped in a window that has ended, with the code and message.
+struct Suppressed {
+    code: String,
+    message: String,
+    count: u64,
+    level: Severity,
+    dispatch: Dispatch,
+}
+
+/// Emits an `error response` event with the code, status, message and source
+/// chain of the context, unless it is dropped by the [`LogSampling`].
+pub(crate) fn log_response(level: Severity, ctx: &ErrorResponseContext) {
+    let emit = match LOG_SAMPLING.get() {
+        Some(sampling) => sample(sampling, level, ctx),
+        None => true,
+    };
+
+    if emit {
+        emit_response(level, ctx);
+    }
+}
+
+fn emit_response(level: Severity, ctx: &ErrorResponseContext) {
+    macro_rules! emit {
+        ($level:expr) => {
+            tracing::event!(
+                $level,
+                error.code = ctx.code(),
+                http.status = ctx.status_code().as_u16(),
+                error.message = ctx.message(),
+                error.sources = ?ctx.sources(),
+                "error response"
+            )
+        };
+    }
+
+    match level {
+        Severity::Info => emit!(Level::INFO),
+        Severity::Warn => emit!(Level::WARN),
+        Severity::Error => emit!(Level::ERROR),
+    }
+}
+
+fn emit_suppressed(suppressed: Suppressed) {
+    macro_rules! emit {
+        ($level:expr) => {
+            tracing::event!(
+                $level,
+                error.code = suppressed.code,
+                error.message = suppressed.message,
+                suppressed = suppressed.count,
+                "error responses suppressed"
+            )
+        };
+    }
+
+    dispatcher::with_default(&suppressed.dispatch, || match suppressed.level {
+        Severity::Info => emit!(Level::INFO),
+        Severity::Warn => emit!(Level::WARN),
+        Severity::Error => emit!(Level::ERROR),
+    })
+}
+
+fn lock_windows() -> MutexGuard<'static, Windows> {
+    WINDOWS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Removes the windows that have ended, returning what they dropped.
+fn close_ended(windows: &mut Windows, sampling: &LogSampling, now: Instant) -> Vec<Suppressed> {
+    let mut ended = Vec::new();
+
+    windows.open.retain(|(code, message), window| {
+        let open = now.duration_since(window.started) < sampling.window;
+
+        if !open && window.suppressed > 0 {
+            ended.push(Suppressed {
+                code: code.clone(),
+                message: message.clone(),
+                count: window.suppressed,
+                level: window.level,
+                dispatch: window.dispatch.clone(),
+            });
+        }
+
+        open
+    });
+    windows.next_sweep = Some(now + sampling.window);
+
+    ended
+}
+
+/// Counts the event in its window and tells whether it is emitted. Windows
+/// that have ended are closed first, at most once per window, reporting what
+/// they dropped.
+fn sample(sampling: &LogSampling, level: Severity, ctx: &ErrorResponseContext) -> bool {
+    let now = Instant::now();
+    let key = (ctx.code().to_string(), ctx.message().to_string());
+    let mut windows = lock_windows();
+
+    let ended = match windows.next_sweep {
+        Some(next_sweep) if now < next_sweep => Vec::new(),
+        _ => close_ended(&mut windows, sampling, now),
+    };
+
+    let window = windows.open.entry(key).or_insert_with(|| Window {
+        started: now,
+        logged: 0,
+        suppressed: 0,
+        level,
+        dispatch: dispatcher::get_default(Dispatch::clone),
+    });
+
+    let emit = if window.logged < sampling.max_events {
+        window.logged += 1;
+        true
+    } else {
+        window.suppressed += 1;
+        false
+    };
+    drop(windows);
+
+    for suppressed in ended {
+        emit_suppressed(suppressed);
+    }
+
+    emit
+}
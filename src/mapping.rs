@@ -0,0 +1,134 @@
+//! Runtime mappings for errors that do not implement
+//! [`IntoErrorResponseContext`](crate::IntoErrorResponseContext), consulted by
+//! `#[response(nested_or_map)]` variants.
+
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    error::Error,
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
+use crate::{
+    sources::{ErrorSources, SourceChain},
+    ErrorResponseContext, ToErrorResponseContext,
+};
+
+/// Converts a wrapped error into the context it responds with. The error can
+/// be downcast to the type it was registered for.
+pub type ErrorMapper = fn(&(dyn Error + 'static)) -> ErrorResponseContext;
+
+/// A registered mapper, with the check telling whether a type-erased error is
+/// of the type it was registered for.
+#[derive(Clone, Copy)]
+struct Mapping {
+    #[cfg_attr(
+        not(any(feature = "axum", feature = "anyhow", feature = "eyre")),
+        allow(dead_code)
+    )]
+    matches: fn(&(dyn Error + 'static)) -> bool,
+    mapper: ErrorMapper,
+}
+
+static MAPPERS: OnceLock<RwLock<HashMap<TypeId, Mapping>>> = OnceLock::new();
+static FALLBACK_MAPPER: OnceLock<ErrorMapper> = OnceLock::new();
+
+/// Registers how errors of type `E` respond when wrapped by a
+/// `#[response(nested_or_map)]` variant, e.g. `io::Error` or `axum::Error`,
+/// which would otherwise become a 500.
+///
+/// Returns the mapper previously registered for `E`, if any.
+///
+/// ```rust,ignore
+/// axum_error_handler::register_error_mapper::<std::io::Error>(|err| {
+///     match err.downcast_ref::<std::io::Error>().map(|err| err.kind()) {
+///         Some(std::io::ErrorKind::NotFound) => ErrorResponseContext::not_found(),
+///         _ => ErrorResponseContext::internal_server_error(),
+///     }
+/// });
+/// ```
+pub fn register_error_mapper<E: Error + 'static>(mapper: ErrorMapper) -> Option<ErrorMapper> {
+    let mapping = Mapping {
+        matches: |err| err.is::<E>(),
+        mapper,
+    };
+
+    MAPPERS
+        .get_or_init(Default::default)
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(TypeId::of::<E>(), mapping)
+        .map(|mapping| mapping.mapper)
+}
+
+/// Registers an error type that converts itself, such as a derived error
+/// enum, so it is found when wrapped in a type-erased error like
+/// `anyhow::Error`.
+///
+/// Returns the mapper previously registered for `E`, if any.
+pub fn register_error_type<E: Error + ToErrorResponseContext + 'static>() -> Option<ErrorMapper> {
+    register_error_mapper::<E>(|err| match err.downcast_ref::<E>() {
+        Some(err) => err.to_response_context(),
+        None => fallback_context(err),
+    })
+}
+
+/// Sets how errors without a registered mapper respond, both in
+/// `#[response(nested_or_map)]` variants and in type-erased errors. Defaults
+/// to a 500 whose sources are the error and its `source()` chain.
+///
+/// Can only be set once; later calls return the rejected value.
+pub fn set_fallback_mapper(mapper: ErrorMapper) -> Result<(), ErrorMapper> {
+    FALLBACK_MAPPER.set(mapper)
+}
+
+/// Maps `err` with the mapper registered for `E`. Unmapped errors become a
+/// 500 whose sources are the error and its `source()` chain.
+pub(crate) fn map_error<E: Error + 'static>(err: &E) -> ErrorResponseContext {
+    let mapper = MAPPERS.get().and_then(|mappers| {
+        mappers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&TypeId::of::<E>())
+            .copied()
+    });
+
+    match mapper {
+        Some(mapping) => (mapping.mapper)(err),
+        None => fallback_context(err),
+    }
+}
+
+/// Maps the first error of `chain` that a mapper was registered for, e.g. the
+/// errors wrapped by an `anyhow::Error` from the outermost to the root cause.
+#[cfg_attr(
+    not(any(feature = "axum", feature = "anyhow", feature = "eyre")),
+    allow(dead_code)
+)]
+pub(crate) fn map_chain<'a>(
+    chain: impl IntoIterator<Item = &'a (dyn Error + 'static)>,
+) -> Option<ErrorResponseContext> {
+    let mappers = MAPPERS
+        .get()?
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    chain.into_iter().find_map(|err| {
+        mappers
+            .values()
+            .find(|mapping| (mapping.matches)(err))
+            .map(|mapping| (mapping.mapper)(err))
+    })
+}
+
+/// Maps `err` with the mapper set by [`set_fallback_mapper`].
+pub(crate) fn fallback_context(err: &(dyn Error + 'static)) -> ErrorResponseContext {
+    if let Some(mapper) = FALLBACK_MAPPER.get() {
+        return mapper(err);
+    }
+
+    let mut sources = vec![err.to_string()];
+    sources.extend(SourceChain(err).sources());
+
+    ErrorResponseContext::internal_server_error().override_with(|builder| builder.sources(sources))
+}
@@ -0,0 +1,15 @@
+//! Counters incremented whenever a context is rendered.
+
+use crate::ErrorResponseContext;
+
+/// Increments `error_responses_total`, labelled with the context's code and
+/// status. The code is the context's own, also for internal errors whose
+/// code is hidden from clients.
+pub(crate) fn record_response(ctx: &ErrorResponseContext) {
+    ::metrics::counter!(
+        "error_responses_total",
+        "code" => ctx.code().to_string(),
+        "status" => ctx.status_code().as_str().to_string(),
+    )
+    .increment(1);
+}
@@ -0,0 +1,41 @@
+//! Responses for axum's multipart extractor. The parser's messages are kept
+//! in the sources for logging, but clients only see whether the body was
+//! malformed or too large.
+
+use axum::extract::multipart::{MultipartError, MultipartRejection};
+use http::StatusCode;
+
+use crate::{
+    rejection::{impl_into_error_response_context, rejection_context},
+    sources::{ErrorSources, SourceChain},
+    ErrorResponseBuilder, ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+/// A 413 `PAYLOAD_TOO_LARGE` when a field or the whole body is over its
+/// limit, a 400 `MULTIPART_INVALID` when the body is malformed, and a
+/// redacted 500 when reading the body failed.
+impl ToErrorResponseContext for MultipartError {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let mut sources = vec![self.body_text()];
+        sources.extend(SourceChain(self).sources());
+
+        match self.status() {
+            StatusCode::BAD_REQUEST => ErrorResponseBuilder::new()
+                .status_code(StatusCode::BAD_REQUEST)
+                .code("MULTIPART_INVALID")
+                .message("Invalid multipart body")
+                .sources(sources)
+                .build(),
+            status => ErrorResponseContext::from_status(status)
+                .override_with(|builder| builder.sources(sources)),
+        }
+    }
+}
+
+impl ToErrorResponseContext for MultipartRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        rejection_context(self, self.status(), "MULTIPART_INVALID", self.body_text())
+    }
+}
+
+impl_into_error_response_context!(MultipartError, MultipartRejection);
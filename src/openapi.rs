@@ -0,0 +1,155 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use serde_json::Value;
+use utoipa::openapi::{
+    example::ExampleBuilder,
+    response::{Response, ResponseBuilder},
+    schema::{ArrayBuilder, ObjectBuilder, Schema, Type},
+    ContentBuilder, RefOr,
+};
+
+use crate::{
+    config::{client_code, client_message, client_title},
+    format, BodyEncoding, ErrorFormat, ErrorResponseContext,
+};
+
+/// Builds the schema of the bodies rendered for `contexts`, e.g. one context
+/// per variant of an error enum. The shape follows the format and envelope of
+/// the first context, and the member holding the code only accepts the codes
+/// of the given contexts.
+pub fn error_schema(contexts: &[ErrorResponseContext]) -> RefOr<Schema> {
+    let codes = codes(contexts);
+
+    match contexts.first() {
+        Some(ctx) => body_schema(ctx, &codes),
+        None => ObjectBuilder::new().into(),
+    }
+}
+
+/// Builds one OpenAPI response per status code found in `contexts`, with the
+/// body schema of [`error_schema`] and an example body for every code.
+pub fn error_responses(contexts: &[ErrorResponseContext]) -> BTreeMap<String, RefOr<Response>> {
+    let mut by_status = BTreeMap::<u16, Vec<&ErrorResponseContext>>::new();
+
+    for ctx in contexts {
+        by_status
+            .entry(ctx.status_code().as_u16())
+            .or_default()
+            .push(ctx);
+    }
+
+    by_status
+        .into_iter()
+        .map(|(status, contexts)| {
+            let first = contexts[0];
+            let codes = contexts
+                .iter()
+                .map(|ctx| client_code(ctx).to_string())
+                .collect::<Vec<_>>();
+            let reason = first.status_code().canonical_reason().unwrap_or_default();
+            let description = match contexts.as_slice() {
+                [ctx] => summary(ctx).unwrap_or(reason.into()),
+                _ => reason.into(),
+            };
+            let examples = contexts.iter().filter_map(|ctx| {
+                let example = ExampleBuilder::new()
+                    .summary(summary(ctx).unwrap_or(reason.into()))
+                    .value(Some(example_body(ctx)?))
+                    .build();
+                Some((client_code(ctx).to_string(), example))
+            });
+            let content = ContentBuilder::new()
+                .schema(Some(body_schema(first, &codes)))
+                .examples_from_iter(examples)
+                .build();
+            let response = ResponseBuilder::new()
+                .description(description)
+                .content(content_type(first), content)
+                .build();
+
+            (status.to_string(), response.into())
+        })
+        .collect()
+}
+
+fn summary(ctx: &ErrorResponseContext) -> Option<Cow<'_, str>> {
+    client_title(ctx)
+        .map(Cow::Borrowed)
+        .or_else(|| Some(client_message(ctx)).filter(|message| !message.is_empty()))
+}
+
+fn codes(contexts: &[ErrorResponseContext]) -> Vec<String> {
+    let mut codes = Vec::new();
+
+    for ctx in contexts {
+        if !codes.iter().any(|code| code == client_code(ctx)) {
+            codes.push(client_code(ctx).to_string());
+        }
+    }
+
+    codes
+}
+
+fn content_type(ctx: &ErrorResponseContext) -> String {
+    if let Some(content_type) = ctx.content_type() {
+        return content_type.to_string();
+    }
+
+    match ctx.format() {
+        ErrorFormat::Text => "text/plain".to_string(),
+        #[cfg(feature = "html")]
+        ErrorFormat::Html => "text/html".to_string(),
+        format => {
+            let encoding = ctx.encoding().unwrap_or(BodyEncoding::Json);
+            let content_type = encoding.content_type(format);
+            content_type.to_str().unwrap_or_default().to_string()
+        }
+    }
+}
+
+/// The body rendered for `ctx`, for the formats that have a fixed shape.
+fn example_body(ctx: &ErrorResponseContext) -> Option<Value> {
+    match ctx.format() {
+        ErrorFormat::Text => Some(format::text_body(ctx).into()),
+        #[cfg(feature = "html")]
+        ErrorFormat::Html => None,
+        _ => Some(format::structured_body(ctx)),
+    }
+}
+
+/// Infers the schema from the body rendered for `ctx`. Strings equal to one
+/// of `codes` are described as an enum of the codes.
+fn body_schema(ctx: &ErrorResponseContext, codes: &[String]) -> RefOr<Schema> {
+    match example_body(ctx) {
+        Some(body) => value_schema(&body, codes),
+        None => ObjectBuilder::new().schema_type(Type::String).into(),
+    }
+}
+
+fn value_schema(value: &Value, codes: &[String]) -> RefOr<Schema> {
+    match value {
+        Value::Null => ObjectBuilder::new().schema_type(Type::Null).into(),
+        Value::Bool(_) => ObjectBuilder::new().schema_type(Type::Boolean).into(),
+        Value::Number(number) if number.is_f64() => {
+            ObjectBuilder::new().schema_type(Type::Number).into()
+        }
+        Value::Number(_) => ObjectBuilder::new().schema_type(Type::Integer).into(),
+        Value::String(string) if codes.contains(string) => ObjectBuilder::new()
+            .schema_type(Type::String)
+            .enum_values(Some(codes.iter().cloned()))
+            .into(),
+        Value::String(_) => ObjectBuilder::new().schema_type(Type::String).into(),
+        Value::Array(items) => match items.first() {
+            Some(item) => ArrayBuilder::new().items(value_schema(item, codes)).into(),
+            None => ArrayBuilder::new().into(),
+        },
+        Value::Object(members) => members
+            .iter()
+            .fold(ObjectBuilder::new(), |object, (name, value)| {
+                object
+                    .property(name, value_schema(value, codes))
+                    .required(name)
+            })
+            .into(),
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    any::Any,
+    fmt,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::ErrorResponseBuilder;
+
+/// Layer that turns panics in the services it wraps into a 500 with code
+/// `INTERNAL_PANIC`, rendered in the crate's format instead of tower-http's
+/// plain text body. The panic message is kept out of the message and only
+/// appears in the sources, which are rendered when causes are included.
+///
+/// ```rust,ignore
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .layer(CatchPanicLayer::new())
+///     .layer(RequestContextLayer::new());
+/// ```
+///
+/// Add it inside [`RequestContextLayer`](crate::RequestContextLayer) so the
+/// body carries the request's trace id.
+#[derive(Debug, Clone, Default)]
+pub struct CatchPanicLayer;
+
+impl CatchPanicLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for CatchPanicLayer {
+    type Service = CatchPanicService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CatchPanicService { inner }
+    }
+}
+
+/// Service produced by [`CatchPanicLayer`].
+#[derive(Debug, Clone)]
+pub struct CatchPanicService<S> {
+    inner: S,
+}
+
+impl<S, R> Service<R> for CatchPanicService<S>
+where
+    S: Service<R, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = CatchPanicFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        match catch_unwind(AssertUnwindSafe(|| self.inner.call(req))) {
+            Ok(inner) => CatchPanicFuture {
+                inner: Some(inner),
+                panic: None,
+            },
+            Err(panic) => CatchPanicFuture {
+                inner: None,
+                panic: Some(panic),
+            },
+        }
+    }
+}
+
+pin_project! {
+    /// Response future of [`CatchPanicService`].
+    pub struct CatchPanicFuture<F> {
+        #[pin]
+        inner: Option<F>,
+        panic: Option<Box<dyn Any + Send>>,
+    }
+}
+
+impl<F> fmt::Debug for CatchPanicFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchPanicFuture").finish_non_exhaustive()
+    }
+}
+
+impl<F, E> Future for CatchPanicFuture<F>
+where
+    F: Future<Output = Result<Response, E>>,
+{
+    type Output = Result<Response, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let panic = match this.inner.as_pin_mut() {
+            Some(inner) => match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+                Ok(poll) => return poll,
+                Err(panic) => panic,
+            },
+            None => this
+                .panic
+                .take()
+                .expect("CatchPanicFuture polled after completion"),
+        };
+
+        Poll::Ready(Ok(panic_response(panic)))
+    }
+}
+
+fn panic_response(panic: Box<dyn Any + Send>) -> Response {
+    let message = match panic.downcast::<String>() {
+        Ok(message) => Some(*message),
+        Err(panic) => panic
+            .downcast::<&str>()
+            .ok()
+            .map(|message| message.to_string()),
+    };
+
+    ErrorResponseBuilder::new()
+        .status_code(StatusCode::INTERNAL_SERVER_ERROR)
+        .code("INTERNAL_PANIC")
+        .message("Internal Server Error")
+        .sources(message.into_iter().collect())
+        .build()
+        .into_response()
+}
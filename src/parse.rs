@@ -0,0 +1,258 @@
+use std::{error::Error, fmt, time::Duration};
+
+use axum::{body::to_bytes, http::header, response::Response};
+use serde_json::{Map, Value};
+
+use crate::{
+    ChainEntry, EnvelopeConfig, ErrorFormat, ErrorResponseBuilder, ErrorResponseContext, FieldError,
+};
+
+/// Why [`ErrorResponseContext::from_response`] could not read a response.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ParseResponseError {
+    /// The body could not be collected.
+    Body(axum::Error),
+    /// The body is not valid JSON.
+    Json(serde_json::Error),
+    /// The body does not match any of the error formats.
+    Unrecognized,
+}
+
+impl fmt::Display for ParseResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseResponseError::Body(err) => write!(f, "failed to read the response body: {err}"),
+            ParseResponseError::Json(err) => write!(f, "response body is not valid JSON: {err}"),
+            ParseResponseError::Unrecognized => {
+                f.write_str("response body is not a recognized error format")
+            }
+        }
+    }
+}
+
+impl Error for ParseResponseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseResponseError::Body(err) => Some(err),
+            ParseResponseError::Json(err) => Some(err),
+            ParseResponseError::Unrecognized => None,
+        }
+    }
+}
+
+impl ErrorResponseContext {
+    /// Reads an error response back into a context, so tests can assert on
+    /// its members instead of matching JSON strings.
+    ///
+    /// Understands JSON bodies in every [`ErrorFormat`] and plain-text bodies.
+    /// Members without a dedicated field come back as extension members.
+    ///
+    /// ```rust,ignore
+    /// let ctx = ErrorResponseContext::from_response(app.oneshot(req).await?).await?;
+    /// assert_eq!(ctx.code(), "USER_NOT_FOUND");
+    /// ```
+    pub async fn from_response(response: Response) -> Result<Self, ParseResponseError> {
+        let (parts, body) = response.into_parts();
+        let bytes = to_bytes(body, usize::MAX)
+            .await
+            .map_err(ParseResponseError::Body)?;
+
+        let mut builder = ErrorResponseBuilder::new().status_code(parts.status);
+
+        let retry_after = parts
+            .headers
+            .get(header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok()?.parse().ok());
+        if let Some(secs) = retry_after {
+            builder = builder.retry_after(Duration::from_secs(secs));
+        }
+
+        let is_text = parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("text/plain"));
+        if is_text {
+            let text = String::from_utf8_lossy(&bytes);
+            let (code, message) = text
+                .split_once(": ")
+                .ok_or(ParseResponseError::Unrecognized)?;
+
+            return Ok(builder
+                .format(ErrorFormat::Text)
+                .code(code)
+                .message(message)
+                .build());
+        }
+
+        let body = match serde_json::from_slice(&bytes).map_err(ParseResponseError::Json)? {
+            Value::Object(body) => body,
+            _ => return Err(ParseResponseError::Unrecognized),
+        };
+
+        let builder = if body.get("errors").is_some_and(Value::is_array) {
+            parse_errors_array(builder, body)?
+        } else if body.contains_key("status") && body.contains_key("detail") {
+            parse_problem(builder, body)
+        } else {
+            parse_envelope(builder, body)?
+        };
+
+        Ok(builder.build())
+    }
+}
+
+fn parse_envelope(
+    builder: ErrorResponseBuilder,
+    mut body: Map<String, Value>,
+) -> Result<ErrorResponseBuilder, ParseResponseError> {
+    let config = EnvelopeConfig::global();
+
+    let mut error = match body.remove(config.error_member_name()) {
+        Some(Value::Object(error)) => error,
+        Some(_) => return Err(ParseResponseError::Unrecognized),
+        None => body,
+    };
+
+    let code = take_string(&mut error, config.code_member_name())
+        .ok_or(ParseResponseError::Unrecognized)?;
+    let message = take_string(&mut error, config.message_member_name()).unwrap_or_default();
+
+    Ok(apply_members(builder.code(code).message(message), error))
+}
+
+fn parse_problem(
+    mut builder: ErrorResponseBuilder,
+    mut body: Map<String, Value>,
+) -> ErrorResponseBuilder {
+    body.remove("status");
+
+    if let Some(type_uri) = take_string(&mut body, "type").filter(|uri| uri != "about:blank") {
+        builder = builder.type_uri(type_uri);
+    }
+    if let Some(title) = take_string(&mut body, "title") {
+        builder = builder.title(title);
+    }
+    if let Some(instance) = take_string(&mut body, "instance") {
+        builder = builder.instance(instance);
+    }
+
+    let code = take_string(&mut body, "code").unwrap_or_default();
+    let message = take_string(&mut body, "detail").unwrap_or_default();
+
+    apply_members(
+        builder
+            .format(ErrorFormat::Problem)
+            .code(code)
+            .message(message),
+        body,
+    )
+}
+
+/// GraphQL and JSON:API bodies, which both carry an `errors` array. Only the
+/// first error is read.
+fn parse_errors_array(
+    mut builder: ErrorResponseBuilder,
+    mut body: Map<String, Value>,
+) -> Result<ErrorResponseBuilder, ParseResponseError> {
+    let mut error = match body.remove("errors") {
+        Some(Value::Array(errors)) => match errors.into_iter().next() {
+            Some(Value::Object(error)) => error,
+            _ => return Err(ParseResponseError::Unrecognized),
+        },
+        _ => return Err(ParseResponseError::Unrecognized),
+    };
+
+    if let Some(message) = take_string(&mut error, "message") {
+        let mut extensions = match error.remove("extensions") {
+            Some(Value::Object(extensions)) => extensions,
+            _ => Map::new(),
+        };
+        let code = take_string(&mut extensions, "code").unwrap_or_default();
+
+        return Ok(apply_members(
+            builder
+                .format(ErrorFormat::GraphQl)
+                .code(code)
+                .message(message),
+            extensions,
+        ));
+    }
+
+    #[cfg(feature = "jsonapi")]
+    {
+        builder = builder.format(ErrorFormat::JsonApi);
+    }
+
+    if let Some(title) = take_string(&mut error, "title") {
+        builder = builder.title(title);
+    }
+    if let Some(source) = error.remove("source") {
+        builder = builder.extension_member("source", source);
+    }
+
+    let help_url = error
+        .get("links")
+        .and_then(|links| links.get("about")?.as_str());
+    if let Some(help_url) = help_url {
+        builder = builder.help_url(help_url);
+    }
+
+    let code = take_string(&mut error, "code").unwrap_or_default();
+    let message = take_string(&mut error, "detail").unwrap_or_default();
+    let meta = match error.remove("meta") {
+        Some(Value::Object(meta)) => meta,
+        _ => Map::new(),
+    };
+
+    Ok(apply_members(builder.code(code).message(message), meta))
+}
+
+/// Maps the members shared by every format back onto the builder. Anything
+/// else becomes an extension member.
+fn apply_members(
+    mut builder: ErrorResponseBuilder,
+    members: Map<String, Value>,
+) -> ErrorResponseBuilder {
+    for (name, value) in members {
+        builder = match (name.as_str(), value) {
+            // Restored from the `Retry-After` header; the timestamp is not
+            // part of the identity of the error.
+            ("retry_after" | "timestamp", _) => builder,
+            ("help_url", Value::String(help_url)) => builder.help_url(help_url),
+            ("details", details) => builder.details(details),
+            ("message_key", Value::String(message_key)) => builder.message_key(message_key),
+            ("message_args", Value::Object(args)) => {
+                args.into_iter().fold(builder, |builder, (name, value)| {
+                    builder.message_arg(name, value)
+                })
+            }
+            ("trace_id", Value::String(trace_id)) => builder.trace_id(trace_id),
+            ("fields", fields) => match serde_json::from_value::<Vec<FieldError>>(fields.clone()) {
+                Ok(field_errors) => builder.field_errors(field_errors),
+                Err(_) => builder.extension_member("fields", fields),
+            },
+            ("chain", chain) => match serde_json::from_value::<Vec<ChainEntry>>(chain.clone()) {
+                Ok(chain) => builder.chain(chain),
+                Err(_) => builder.extension_member("chain", chain),
+            },
+            ("causes", Value::Array(causes)) => builder.sources(
+                causes
+                    .into_iter()
+                    .filter_map(|cause| cause.as_str().map(str::to_string))
+                    .collect(),
+            ),
+            (_, value) => builder.extension_member(name.clone(), value),
+        };
+    }
+
+    builder
+}
+
+fn take_string(object: &mut Map<String, Value>, key: &str) -> Option<String> {
+    match object.remove(key)? {
+        Value::String(value) => Some(value),
+        _ => None,
+    }
+}
@@ -0,0 +1,171 @@
+//! Support items for code generated by the derive. Not public API.
+
+use std::{error::Error, time::Duration};
+
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+
+#[cfg(feature = "actix")]
+pub use actix_web;
+pub use http;
+pub use inventory;
+
+#[cfg(feature = "actix")]
+pub use crate::actix::actix_status;
+
+pub use crate::catalog::CatalogRegistration;
+pub use crate::sources::{ErrorSources, NoSources, SourceChain};
+use crate::{
+    ChainEntry, ErrorResponseContext, IntoErrorResponseContext, Severity, ToErrorResponseContext,
+};
+
+/// Return types accepted from `custom_fn` renderers: a response, or a result
+/// whose error is rendered by the default renderer instead.
+#[cfg(feature = "axum")]
+pub trait RenderedResponse {
+    fn into_rendered_response(self) -> Response;
+}
+
+#[cfg(feature = "axum")]
+impl RenderedResponse for Response {
+    fn into_rendered_response(self) -> Response {
+        self
+    }
+}
+
+#[cfg(feature = "axum")]
+impl RenderedResponse for Result<Response, ErrorResponseContext> {
+    fn into_rendered_response(self) -> Response {
+        self.unwrap_or_else(IntoResponse::into_response)
+    }
+}
+
+/// Conversion of the field of a `#[response(nested_or_map)]` variant, through
+/// autoref specialization: `NestedOrMap(inner).nested_context()` uses the
+/// inner error's own context when it implements `IntoErrorResponseContext`,
+/// and the mapper registered for its type otherwise.
+pub struct NestedOrMap<T>(pub T);
+
+pub trait NestedContext {
+    fn nested_context(self) -> ErrorResponseContext;
+}
+
+impl<T: IntoErrorResponseContext> NestedContext for NestedOrMap<T> {
+    fn nested_context(self) -> ErrorResponseContext {
+        self.0.into_response_context()
+    }
+}
+
+pub trait MappedContext {
+    fn nested_context(self) -> ErrorResponseContext;
+}
+
+impl<T: Error + 'static> MappedContext for &NestedOrMap<T> {
+    fn nested_context(self) -> ErrorResponseContext {
+        crate::mapping::map_error(&self.0)
+    }
+}
+
+/// Level declared with `#[response(log = "...")]`.
+pub enum LogLevel {
+    /// No `log` attribute: the context's severity, which defaults to `warn`
+    /// for 4xx and `error` for 5xx.
+    Default,
+    At(Severity),
+    Off,
+}
+
+/// Reports a derived error converted into a response to the enabled
+/// integrations: a `tracing` event at `level`, and a Sentry event for 5xx.
+#[allow(unused_variables)]
+pub fn report(level: LogLevel, ctx: &ErrorResponseContext) {
+    #[cfg(feature = "tracing")]
+    {
+        let level = match level {
+            LogLevel::Default => Some(ctx.severity()),
+            LogLevel::At(level) => Some(level),
+            LogLevel::Off => None,
+        };
+
+        if let Some(level) = level {
+            crate::logging::log_response(level, ctx);
+        }
+    }
+
+    #[cfg(feature = "sentry")]
+    crate::sentry::capture(ctx);
+}
+
+/// Records the nested variant with `code` and `message` as the outermost level
+/// of the chain of `inner`, the context of the error it wraps.
+pub fn nest(inner: ErrorResponseContext, code: String, message: String) -> ErrorResponseContext {
+    let mut chain = vec![ChainEntry::new(code, message)];
+
+    match inner.chain() {
+        [] => chain.push(ChainEntry::new(inner.code(), inner.message())),
+        levels => chain.extend_from_slice(levels),
+    }
+
+    inner.override_with(|builder| builder.chain(chain))
+}
+
+/// Borrowed conversion of a nested variant's field, used when the enum is
+/// converted through `ToErrorResponseContext`. Through autoref specialization,
+/// `(&&&NestedRef(inner)).nested_context()` borrows the inner error's context
+/// when it implements `ToErrorResponseContext`, falls back to the registered
+/// mapper for other errors, and to a 500 for anything else.
+pub struct NestedRef<'a, T>(pub &'a T);
+
+pub trait BorrowedContext {
+    fn nested_context(&self) -> ErrorResponseContext;
+}
+
+impl<T: ToErrorResponseContext> BorrowedContext for &&NestedRef<'_, T> {
+    fn nested_context(&self) -> ErrorResponseContext {
+        self.0.to_response_context()
+    }
+}
+
+pub trait BorrowedMappedContext {
+    fn nested_context(&self) -> ErrorResponseContext;
+}
+
+impl<T: Error + 'static> BorrowedMappedContext for &NestedRef<'_, T> {
+    fn nested_context(&self) -> ErrorResponseContext {
+        crate::mapping::map_error(self.0)
+    }
+}
+
+pub trait UnmappedContext {
+    fn nested_context(&self) -> ErrorResponseContext {
+        ErrorResponseContext::internal_server_error()
+    }
+}
+
+impl<T> UnmappedContext for NestedRef<'_, T> {}
+
+/// Field types accepted by `#[response(retry_after_field = "...")]`: whole
+/// seconds or a `Duration`.
+pub trait IntoRetryAfter {
+    fn into_retry_after(self) -> Duration;
+}
+
+impl IntoRetryAfter for Duration {
+    fn into_retry_after(self) -> Duration {
+        self
+    }
+}
+
+macro_rules! impl_into_retry_after {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoRetryAfter for $ty {
+                fn into_retry_after(self) -> Duration {
+                    Duration::from_secs(self.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_into_retry_after!(u8, u16, u32, u64);
@@ -0,0 +1,39 @@
+//! Responses for `redis` errors. Failures to reach the server or a cluster
+//! that is not ready become a 503, so a cache outage degrades into a
+//! retryable response; everything else is a redacted 500.
+
+use http::StatusCode;
+use redis::{ErrorKind, RedisError};
+
+use crate::{
+    sources::{ErrorSources, SourceChain},
+    ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+impl ToErrorResponseContext for RedisError {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let unavailable = self.is_io_error()
+            || self.is_cluster_error()
+            || matches!(
+                self.kind(),
+                ErrorKind::BusyLoadingError
+                    | ErrorKind::MasterDown
+                    | ErrorKind::ClusterConnectionNotFound
+            );
+        let status = match unavailable {
+            true => StatusCode::SERVICE_UNAVAILABLE,
+            false => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        let mut sources = vec![self.to_string()];
+        sources.extend(SourceChain(self).sources());
+
+        ErrorResponseContext::from_status(status).override_with(|builder| builder.sources(sources))
+    }
+}
+
+impl IntoErrorResponseContext for RedisError {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
@@ -0,0 +1,89 @@
+//! Contexts for axum's extractor rejections, so a failed extractor renders in
+//! the same format as the application's own errors instead of axum's plain
+//! text bodies.
+
+use std::error::Error;
+
+use axum::extract::rejection::{ExtensionRejection, JsonRejection, PathRejection, QueryRejection};
+
+use crate::{
+    sources::{ErrorSources, SourceChain},
+    ErrorResponseBuilder, ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+/// Uses the rejection's own status and body text, with `code` as the code.
+pub(crate) fn rejection_context<E: Error>(
+    err: &E,
+    status: http::StatusCode,
+    code: &str,
+    message: String,
+) -> ErrorResponseContext {
+    ErrorResponseBuilder::new()
+        .status_code(status)
+        .code(code)
+        .message(message)
+        .sources(SourceChain(err).sources())
+        .build()
+}
+
+impl ToErrorResponseContext for JsonRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let code = match self {
+            JsonRejection::JsonDataError(_) => "INVALID_JSON_DATA",
+            JsonRejection::MissingJsonContentType(_) => "MISSING_JSON_CONTENT_TYPE",
+            JsonRejection::BytesRejection(_) => "INVALID_REQUEST_BODY",
+            _ => "INVALID_JSON_BODY",
+        };
+
+        rejection_context(self, self.status(), code, self.body_text())
+    }
+}
+
+impl ToErrorResponseContext for PathRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let code = match self {
+            PathRejection::MissingPathParams(_) => "MISSING_PATH_PARAMS",
+            _ => "INVALID_PATH_PARAMS",
+        };
+
+        rejection_context(self, self.status(), code, self.body_text())
+    }
+}
+
+impl ToErrorResponseContext for QueryRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        rejection_context(
+            self,
+            self.status(),
+            "INVALID_QUERY_STRING",
+            self.body_text(),
+        )
+    }
+}
+
+impl ToErrorResponseContext for ExtensionRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        rejection_context(self, self.status(), "MISSING_EXTENSION", self.body_text())
+    }
+}
+
+macro_rules! impl_into_error_response_context {
+    ($($rejection:ty),*) => {
+        $(
+            impl IntoErrorResponseContext for $rejection {
+                fn into_response_context(self) -> ErrorResponseContext {
+                    self.to_response_context()
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_into_error_response_context;
+
+impl_into_error_response_context!(
+    JsonRejection,
+    PathRejection,
+    QueryRejection,
+    ExtensionRejection
+);
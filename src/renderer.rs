@@ -0,0 +1,55 @@
+use std::sync::OnceLock;
+
+use axum::response::Response;
+
+use crate::ErrorResponseContext;
+
+/// Renders an error context into the response sent to the client.
+pub type DefaultRenderer = fn(ErrorResponseContext) -> Response;
+
+/// Post-processes every response rendered by the built-in renderer.
+pub type ResponseHook = fn(&ErrorResponseContext, Response) -> Response;
+
+static DEFAULT_RENDERER: OnceLock<DefaultRenderer> = OnceLock::new();
+static RESPONSE_HOOK: OnceLock<ResponseHook> = OnceLock::new();
+
+/// Replaces the built-in rendering of every [`ErrorResponseContext`] for the
+/// whole application, including contexts produced by the derive. Enums with a
+/// `custom_fn` or `responder` keep using it.
+///
+/// The renderer can delegate to the built-in one with
+/// [`ErrorResponseContext::into_default_response`].
+///
+/// Can only be set once; later calls return the rejected renderer.
+pub fn set_default_renderer(renderer: DefaultRenderer) -> Result<(), DefaultRenderer> {
+    DEFAULT_RENDERER.set(renderer)
+}
+
+pub(crate) fn default_renderer() -> Option<DefaultRenderer> {
+    DEFAULT_RENDERER.get().copied()
+}
+
+/// Applies `hook` to every response built by the built-in renderer, after the
+/// body and headers are in place, e.g. to add headers or tag responses for
+/// every error in one place. Renderers set with [`set_default_renderer`] only
+/// go through it when they delegate to
+/// [`ErrorResponseContext::into_default_response`].
+///
+/// ```rust,ignore
+/// axum_error_handler::set_response_hook(|ctx, mut response| {
+///     if let Ok(code) = HeaderValue::from_str(ctx.code()) {
+///         response.headers_mut().insert("x-error-code", code);
+///     }
+///     response
+/// })
+/// .unwrap();
+/// ```
+///
+/// Can only be set once; later calls return the rejected hook.
+pub fn set_response_hook(hook: ResponseHook) -> Result<(), ResponseHook> {
+    RESPONSE_HOOK.set(hook)
+}
+
+pub(crate) fn response_hook() -> Option<ResponseHook> {
+    RESPONSE_HOOK.get().copied()
+}
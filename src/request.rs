@@ -0,0 +1,151 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::{Extensions, HeaderMap, Method, Request, Uri};
+use tokio::task::futures::TaskLocalFuture;
+use tower_layer::Layer;
+use tower_service::Service;
+
+tokio::task_local! {
+    static REQUEST: Arc<RequestInfo>;
+}
+
+/// The parts of the incoming request that are visible while an error is
+/// rendered. Captured by [`RequestContextLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    extensions: Extensions,
+}
+
+impl RequestInfo {
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// The request's extensions as they were when the request reached
+    /// [`RequestContextLayer`]; values inserted by inner layers are not
+    /// visible.
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+}
+
+/// Returns the request currently being handled, if the handler runs under
+/// [`RequestContextLayer`].
+pub fn current_request() -> Option<Arc<RequestInfo>> {
+    REQUEST.try_with(Arc::clone).ok()
+}
+
+/// The id clients can quote to find this request in server traces: the trace
+/// id of the request's W3C `traceparent` header, as propagated by
+/// OpenTelemetry, otherwise its `x-request-id` header.
+pub(crate) fn current_trace_id() -> Option<String> {
+    let request = current_request()?;
+    let headers = request.headers();
+
+    if let Some(trace_id) = headers
+        .get("traceparent")
+        .and_then(|value| value.to_str().ok())
+        .and_then(traceparent_trace_id)
+    {
+        return Some(trace_id.to_string());
+    }
+
+    let request_id = headers.get("x-request-id")?.to_str().ok()?;
+
+    Some(request_id.to_string())
+}
+
+/// The trace id of a `traceparent` header,
+/// `{version}-{trace-id}-{parent-id}-{flags}`, unless it is malformed or all
+/// zeros.
+fn traceparent_trace_id(traceparent: &str) -> Option<&str> {
+    let mut parts = traceparent.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let is_hex = |part: &str, len: usize| {
+        part.len() == len
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    };
+
+    if !is_hex(version, 2) || version == "ff" || !is_hex(trace_id, 32) {
+        return None;
+    }
+    if trace_id.bytes().all(|b| b == b'0') {
+        return None;
+    }
+
+    Some(trace_id)
+}
+
+/// Layer that makes the incoming request available to error rendering, so
+/// problem-details bodies get the request path as their `instance` and custom
+/// renderers can read headers, e.g. for locale selection, without the handler
+/// threading it through.
+///
+/// ```rust,ignore
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .layer(RequestContextLayer::new());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestContextLayer;
+
+impl RequestContextLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for RequestContextLayer {
+    type Service = RequestContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestContextService { inner }
+    }
+}
+
+/// Service produced by [`RequestContextLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestContextService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for RequestContextService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<Arc<RequestInfo>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let info = RequestInfo {
+            method: req.method().clone(),
+            uri: req.uri().clone(),
+            headers: req.headers().clone(),
+            extensions: req.extensions().clone(),
+        };
+
+        REQUEST.scope(Arc::new(info), self.inner.call(req))
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    future::Future,
+    hash::BuildHasher,
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{Context, Poll},
+    time::SystemTime,
+};
+
+use http::{HeaderName, HeaderValue, Request, Response};
+use pin_project_lite::pin_project;
+use tokio::task::futures::TaskLocalFuture;
+use tower_layer::Layer;
+use tower_service::Service;
+
+tokio::task_local! {
+    static REQUEST_ID: RequestId;
+}
+
+/// The id of the request being handled, set by [`RequestIdLayer`] and stored
+/// in the request's extensions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(HeaderValue);
+
+impl RequestId {
+    pub fn new(id: HeaderValue) -> Self {
+        Self(id)
+    }
+
+    pub fn header_value(&self) -> &HeaderValue {
+        &self.0
+    }
+
+    /// The id as text, or `None` when the client sent a header that is not
+    /// visible ASCII.
+    pub fn as_str(&self) -> Option<&str> {
+        self.0.to_str().ok()
+    }
+
+    /// A 128-bit random id, formatted as 32 hex digits.
+    fn generate() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let state = RandomState::new();
+        let high = state.hash_one((count, SystemTime::now()));
+        let low = state.hash_one(count);
+        let id = format!("{:016x}{:016x}", high, low);
+
+        Self(HeaderValue::from_str(&id).expect("hex digits are a valid header value"))
+    }
+}
+
+/// The id of the request being handled, if it runs under [`RequestIdLayer`].
+pub(crate) fn current_request_id() -> Option<RequestId> {
+    REQUEST_ID.try_with(RequestId::clone).ok()
+}
+
+/// Layer that gives every request an id, rendered as `request_id` in the body
+/// of every error returned while handling it, so clients can quote it and the
+/// error can be traced server-side.
+///
+/// The id is read from the request's `x-request-id` header, or the one passed
+/// to [`header`](Self::header), usually set by a proxy or tower-http's
+/// `SetRequestIdLayer`, and generated when the header is missing. It is stored
+/// in the request's extensions as a [`RequestId`], set as the request's header
+/// for inner layers and handlers, and echoed in the response's header.
+///
+/// ```rust,ignore
+/// let app = Router::new()
+///     .route("/", get(handler))
+///     .layer(RequestContextLayer::new())
+///     .layer(RequestIdLayer::new());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RequestIdLayer {
+    header: HeaderName,
+}
+
+impl RequestIdLayer {
+    pub fn new() -> Self {
+        Self {
+            header: HeaderName::from_static("x-request-id"),
+        }
+    }
+
+    /// Reads and writes the id in `header` instead of `x-request-id`.
+    pub fn header(mut self, header: HeaderName) -> Self {
+        self.header = header;
+        self
+    }
+}
+
+impl Default for RequestIdLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService {
+            inner,
+            header: self.header.clone(),
+        }
+    }
+}
+
+/// Service produced by [`RequestIdLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+    header: HeaderName,
+}
+
+impl<S, B, ResBody> Service<Request<B>> for RequestIdService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = RequestIdFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let id = match req.headers().get(&self.header) {
+            Some(id) => RequestId::new(id.clone()),
+            None => {
+                let id = RequestId::generate();
+                req.headers_mut()
+                    .insert(self.header.clone(), id.header_value().clone());
+                id
+            }
+        };
+        req.extensions_mut().insert(id.clone());
+
+        RequestIdFuture {
+            inner: REQUEST_ID.scope(id.clone(), self.inner.call(req)),
+            header: Some((self.header.clone(), id)),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future of [`RequestIdService`].
+    pub struct RequestIdFuture<F> {
+        #[pin]
+        inner: TaskLocalFuture<RequestId, F>,
+        header: Option<(HeaderName, RequestId)>,
+    }
+}
+
+impl<F> fmt::Debug for RequestIdFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestIdFuture").finish_non_exhaustive()
+    }
+}
+
+impl<F, ResBody, E> Future for RequestIdFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+{
+    type Output = Result<Response<ResBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut response = match this.inner.poll(cx) {
+            Poll::Ready(Ok(response)) => response,
+            other => return other,
+        };
+
+        if let Some((header, id)) = this.header.take() {
+            response
+                .headers_mut()
+                .entry(header)
+                .or_insert_with(|| id.header_value().clone());
+        }
+
+        Poll::Ready(Ok(response))
+    }
+}
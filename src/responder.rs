@@ -0,0 +1,38 @@
+use axum::response::Response;
+
+use crate::{ErrorResponseContext, RequestInfo};
+
+/// A type-checked renderer, selected with `#[response(responder = MyResponder)]`.
+///
+/// Configuration can be carried in the type, e.g. through associated constants
+/// or generic parameters:
+///
+/// ```rust
+/// use axum::response::{IntoResponse, Response};
+/// use axum_error_handler::{CustomErrorResponder, ErrorResponseContext};
+///
+/// pub struct Branded<const SHOW_CODE: bool>;
+///
+/// impl<const SHOW_CODE: bool> CustomErrorResponder for Branded<SHOW_CODE> {
+///     fn respond(ctx: ErrorResponseContext) -> Response {
+///         let body = match SHOW_CODE {
+///             true => format!("Acme: {} ({})", ctx.message(), ctx.code()),
+///             false => format!("Acme: {}", ctx.message()),
+///         };
+///
+///         (ctx.status_code(), body).into_response()
+///     }
+/// }
+/// ```
+pub trait CustomErrorResponder {
+    fn respond(ctx: ErrorResponseContext) -> Response;
+
+    /// Called by the derive with the request being handled, when the router
+    /// has [`RequestContextLayer`](crate::RequestContextLayer). Override it to
+    /// read headers, the URI or the method; by default it calls
+    /// [`respond`](Self::respond).
+    fn respond_to(ctx: ErrorResponseContext, request: Option<&RequestInfo>) -> Response {
+        let _ = request;
+        Self::respond(ctx)
+    }
+}
@@ -0,0 +1,153 @@
+//! JSON Schemas of the error bodies, for clients that validate error
+//! responses or generate models from them.
+
+use serde_json::{json, Map, Value};
+
+use crate::{catalog::response_codes, EnvelopeConfig};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// The JSON Schema of the default envelope body, following the layout set
+/// with [`set_envelope_config`](crate::set_envelope_config).
+///
+/// The code member only accepts the codes of the
+/// [`catalog`](crate::catalog()), with `INTERNAL_ERROR` for internal variants,
+/// and the codes of the contexts the crate builds for the enabled features,
+/// such as the fallback's `ROUTE_NOT_FOUND` and the rejections'. It accepts
+/// any string when the catalog is empty or has a variant whose code is only
+/// known at runtime. Codes of contexts built by hand are not listed.
+///
+/// ```rust,ignore
+/// let schema = axum_error_handler::envelope_json_schema();
+/// std::fs::write("error.schema.json", schema.to_string())?;
+/// ```
+pub fn envelope_json_schema() -> Value {
+    let config = EnvelopeConfig::global();
+
+    let mut error = json!({
+        "type": "object",
+        "required": [config.code_member_name(), config.message_member_name()],
+    });
+    let mut properties = Map::new();
+    properties.insert(config.code_member_name().to_string(), code_schema());
+    properties.insert(
+        config.message_member_name().to_string(),
+        json!({ "type": "string" }),
+    );
+    properties.extend(optional_properties());
+    error["properties"] = Value::Object(properties);
+
+    let mut schema = match config.is_flat() {
+        true => error,
+        false => {
+            let mut required = Vec::new();
+            let mut properties = Map::new();
+
+            if !config.is_result_omitted() {
+                required.push(config.result_member_name());
+                properties.insert(
+                    config.result_member_name().to_string(),
+                    json!({ "type": "null" }),
+                );
+            }
+
+            required.push(config.error_member_name());
+            properties.insert(config.error_member_name().to_string(), error);
+
+            json!({
+                "type": "object",
+                "required": required,
+                "properties": properties,
+            })
+        }
+    };
+
+    schema["$schema"] = DRAFT.into();
+    schema["title"] = "Error".into();
+    schema
+}
+
+/// The JSON Schema of the RFC 9457 problem-details body, with the code member
+/// limited to the codes of the [`catalog`](crate::catalog()) as in
+/// [`envelope_json_schema`].
+pub fn problem_json_schema() -> Value {
+    let mut properties = Map::new();
+    properties.insert(
+        "type".to_string(),
+        json!({ "type": "string", "format": "uri-reference" }),
+    );
+    properties.insert("title".to_string(), json!({ "type": "string" }));
+    properties.insert(
+        "status".to_string(),
+        json!({ "type": "integer", "minimum": 400, "maximum": 599 }),
+    );
+    properties.insert("detail".to_string(), json!({ "type": "string" }));
+    properties.insert(
+        "instance".to_string(),
+        json!({ "type": "string", "format": "uri-reference" }),
+    );
+    properties.insert("code".to_string(), code_schema());
+    properties.extend(optional_properties());
+
+    json!({
+        "$schema": DRAFT,
+        "title": "Problem",
+        "type": "object",
+        "required": ["type", "title", "status", "detail", "code"],
+        "properties": properties,
+    })
+}
+
+/// A string, limited to the codes clients can receive when all of them are
+/// known.
+fn code_schema() -> Value {
+    match response_codes() {
+        Some(codes) => json!({ "type": "string", "enum": codes }),
+        None => json!({ "type": "string" }),
+    }
+}
+
+/// The members rendered next to the code and message when they are set or
+/// enabled. Extension members are allowed by not restricting other members.
+fn optional_properties() -> Map<String, Value> {
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    let entry = |members: &[&str]| {
+        json!({
+            "type": "object",
+            "required": members,
+            "properties": members
+                .iter()
+                .map(|member| (member.to_string(), json!({ "type": "string" })))
+                .collect::<Map<_, _>>(),
+        })
+    };
+
+    let mut properties = Map::new();
+    properties.insert(
+        "help_url".to_string(),
+        json!({ "type": "string", "format": "uri" }),
+    );
+    properties.insert(
+        "retry_after".to_string(),
+        json!({ "type": "integer", "minimum": 0 }),
+    );
+    properties.insert("details".to_string(), json!({}));
+    properties.insert(
+        "fields".to_string(),
+        json!({ "type": "array", "items": entry(&["field", "code", "message"]) }),
+    );
+    properties.insert("message_key".to_string(), json!({ "type": "string" }));
+    properties.insert("message_args".to_string(), json!({ "type": "object" }));
+    properties.insert("trace_id".to_string(), json!({ "type": "string" }));
+    properties.insert("request_id".to_string(), json!({ "type": "string" }));
+    properties.insert(
+        "timestamp".to_string(),
+        json!({ "type": "string", "format": "date-time" }),
+    );
+    properties.insert("causes".to_string(), strings);
+    properties.insert(
+        "chain".to_string(),
+        json!({ "type": "array", "items": entry(&["code", "message"]) }),
+    );
+    properties
+}
@@ -0,0 +1,38 @@
+//! Responses for SeaORM's `DbErr`, mapped like the `sqlx` errors: missing
+//! records and constraint violations become client errors, connection
+//! failures a 503, and everything else a redacted 500.
+
+use http::StatusCode;
+use sea_orm::{DbErr, SqlErr};
+
+use crate::{
+    sources::{ErrorSources, SourceChain},
+    ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+impl ToErrorResponseContext for DbErr {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let status = match self {
+            DbErr::RecordNotFound(_) => StatusCode::NOT_FOUND,
+            DbErr::Conn(_) | DbErr::ConnectionAcquire(_) => StatusCode::SERVICE_UNAVAILABLE,
+            _ => match self.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => StatusCode::CONFLICT,
+                Some(SqlErr::ForeignKeyConstraintViolation(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+        };
+
+        // The query and the database's message stay in the sources, which
+        // are only rendered when causes are included.
+        let mut sources = vec![self.to_string()];
+        sources.extend(SourceChain(self).sources());
+
+        ErrorResponseContext::from_status(status).override_with(|builder| builder.sources(sources))
+    }
+}
+
+impl IntoErrorResponseContext for DbErr {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
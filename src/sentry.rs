@@ -0,0 +1,52 @@
+//! Sentry capture of server errors when derived errors are converted into
+//! responses.
+
+use sentry_core::protocol::{Event, Exception, Level};
+
+use crate::ErrorResponseContext;
+
+/// Captures contexts with a 5xx status as Sentry events, tagged with the code
+/// and status. The source chain becomes the event's exceptions.
+pub(crate) fn capture(ctx: &ErrorResponseContext) {
+    if !ctx.status_code().is_server_error() {
+        return;
+    }
+
+    let mut event = Event {
+        level: Level::Error,
+        message: Some(ctx.message().to_string()),
+        timestamp: ctx.timestamp(),
+        ..Default::default()
+    };
+
+    event
+        .tags
+        .insert("error.code".to_string(), ctx.code().to_string());
+    event.tags.insert(
+        "http.status".to_string(),
+        ctx.status_code().as_u16().to_string(),
+    );
+
+    if let Some(trace_id) = ctx.trace_id() {
+        event
+            .tags
+            .insert("trace_id".to_string(), trace_id.to_string());
+    }
+
+    // Sentry lists the innermost cause first.
+    let causes = ctx.sources().iter().rev().map(|source| Exception {
+        ty: "source".to_string(),
+        value: Some(source.clone()),
+        ..Default::default()
+    });
+    event.exception = causes
+        .chain(std::iter::once(Exception {
+            ty: ctx.code().to_string(),
+            value: Some(ctx.message().to_string()),
+            ..Default::default()
+        }))
+        .collect::<Vec<_>>()
+        .into();
+
+    sentry_core::capture_event(event);
+}
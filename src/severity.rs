@@ -0,0 +1,26 @@
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// How loudly an error should be reported by logging hooks. Never rendered
+/// into the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Severity {
+    /// Severity used when none is declared: `Error` for server errors, `Warn`
+    /// for client errors and `Info` otherwise.
+    pub fn for_status(status: StatusCode) -> Self {
+        if status.is_server_error() {
+            Severity::Error
+        } else if status.is_client_error() {
+            Severity::Warn
+        } else {
+            Severity::Info
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Collection of an error's `source()` chain for the derive.
+//!
+//! The derive must not require `std::error::Error`, so it dispatches through
+//! autoref specialization: `(&SourceChain(&err)).sources()` walks the chain when
+//! the error implements `Error` and yields nothing otherwise.
+
+use std::error::Error;
+
+pub struct SourceChain<'a, T: ?Sized>(pub &'a T);
+
+pub trait ErrorSources {
+    fn sources(&self) -> Vec<String>;
+}
+
+impl<T: Error + ?Sized> ErrorSources for SourceChain<'_, T> {
+    fn sources(&self) -> Vec<String> {
+        let mut sources = Vec::new();
+        let mut source = self.0.source();
+
+        while let Some(err) = source {
+            sources.push(err.to_string());
+            source = err.source();
+        }
+
+        sources
+    }
+}
+
+pub trait NoSources {
+    fn sources(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+impl<T: ?Sized> NoSources for &SourceChain<'_, T> {}
@@ -0,0 +1,87 @@
+//! Responses for `sqlx` errors. Constraint violations and missing rows become
+//! client errors, pool exhaustion a 503, and everything else a 500 whose
+//! message does not reveal the query or the database's own message.
+
+use std::{error::Error, fmt};
+
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+use http::StatusCode;
+use sqlx::error::ErrorKind;
+
+use crate::{
+    sources::{ErrorSources, SourceChain},
+    ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+};
+
+impl ToErrorResponseContext for sqlx::Error {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let status = match self {
+            sqlx::Error::RowNotFound => StatusCode::NOT_FOUND,
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed => StatusCode::SERVICE_UNAVAILABLE,
+            sqlx::Error::Database(err) => match err.kind() {
+                ErrorKind::UniqueViolation => StatusCode::CONFLICT,
+                ErrorKind::ForeignKeyViolation
+                | ErrorKind::NotNullViolation
+                | ErrorKind::CheckViolation => StatusCode::UNPROCESSABLE_ENTITY,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        // The database's message stays in the sources, which are only
+        // rendered when causes are included.
+        let mut sources = vec![self.to_string()];
+        sources.extend(SourceChain(self).sources());
+
+        ErrorResponseContext::from_status(status).override_with(|builder| builder.sources(sources))
+    }
+}
+
+impl IntoErrorResponseContext for sqlx::Error {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
+
+/// Wraps [`sqlx::Error`] so a handler can return it directly, e.g. `?` on a
+/// query in a handler returning `Result<_, SqlxError>`.
+#[derive(Debug)]
+pub struct SqlxError(pub sqlx::Error);
+
+impl From<sqlx::Error> for SqlxError {
+    fn from(err: sqlx::Error) -> Self {
+        Self(err)
+    }
+}
+
+impl fmt::Display for SqlxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for SqlxError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl ToErrorResponseContext for SqlxError {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        self.0.to_response_context()
+    }
+}
+
+impl IntoErrorResponseContext for SqlxError {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.0.into_response_context()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for SqlxError {
+    fn into_response(self) -> Response {
+        self.into_response_context().into_response()
+    }
+}
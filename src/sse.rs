@@ -0,0 +1,22 @@
+use axum::response::sse::Event;
+
+use crate::{format, ErrorResponseContext};
+
+impl ErrorResponseContext {
+    /// Renders as a server-sent event named `error` whose data is the JSON
+    /// body of the context's format, so a stream can report a failure after
+    /// its `200` was sent:
+    ///
+    /// ```rust,ignore
+    /// let stream = updates.map(|update| match update {
+    ///     Ok(update) => Event::default().json_data(update),
+    ///     Err(err) => Ok(err.into_response_context().into_sse_event()),
+    /// });
+    /// Sse::new(stream)
+    /// ```
+    pub fn into_sse_event(self) -> Event {
+        Event::default()
+            .event("error")
+            .data(format::structured_body(&self).to_string())
+    }
+}
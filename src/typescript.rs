@@ -0,0 +1,123 @@
+//! TypeScript definitions of the error bodies, so frontends can switch over
+//! the backend's error codes exhaustively.
+
+use std::fmt::Write;
+
+use crate::{catalog::response_codes, EnvelopeConfig};
+
+/// Optional members rendered next to the code and message, with their
+/// TypeScript types.
+const OPTIONAL_MEMBERS: &[(&str, &str)] = &[
+    ("help_url", "string"),
+    ("retry_after", "number"),
+    ("details", "unknown"),
+    ("fields", "FieldError[]"),
+    ("message_key", "string"),
+    ("message_args", "Record<string, unknown>"),
+    ("trace_id", "string"),
+    ("request_id", "string"),
+    ("timestamp", "string"),
+    ("causes", "string[]"),
+    ("chain", "ChainEntry[]"),
+];
+
+/// TypeScript definitions of the error bodies: an `ErrorCode` union of the
+/// codes in the [`catalog`](crate::catalog()) and of the contexts the crate
+/// builds for the enabled features, such as `ROUTE_NOT_FOUND`, the
+/// `ErrorBody` members, `ErrorEnvelope` following the layout set with
+/// [`set_envelope_config`](crate::set_envelope_config), and `ProblemDetails`.
+///
+/// `ErrorCode` is `string` when the catalog is empty or has a variant whose
+/// code is only known at runtime. The output is valid as a `.ts` or `.d.ts`
+/// file, written e.g. by a small binary of the application:
+///
+/// ```rust,ignore
+/// fn main() -> std::io::Result<()> {
+///     std::fs::write("web/src/errors.ts", axum_error_handler::typescript_definitions())
+/// }
+/// ```
+pub fn typescript_definitions() -> String {
+    let config = EnvelopeConfig::global();
+    let mut out = String::from("// Generated by axum-error-handler. Do not edit.\n\n");
+
+    match response_codes() {
+        Some(codes) => {
+            let union = codes
+                .iter()
+                .map(|code| format!("\n  | {}", string_literal(code)))
+                .collect::<String>();
+            let _ = writeln!(out, "export type ErrorCode ={};\n", union);
+        }
+        None => out.push_str("export type ErrorCode = string;\n\n"),
+    }
+
+    out.push_str("export interface FieldError {\n");
+    member(&mut out, "field", "string", false);
+    member(&mut out, "code", "string", false);
+    member(&mut out, "message", "string", false);
+    out.push_str("}\n\n");
+
+    out.push_str("export interface ChainEntry {\n");
+    member(&mut out, "code", "string", false);
+    member(&mut out, "message", "string", false);
+    out.push_str("}\n\n");
+
+    out.push_str("export interface ErrorBody {\n");
+    member(&mut out, config.code_member_name(), "ErrorCode", false);
+    member(&mut out, config.message_member_name(), "string", false);
+    optional_members(&mut out);
+    out.push_str("}\n\n");
+
+    match config.is_flat() {
+        true => out.push_str("export type ErrorEnvelope = ErrorBody;\n\n"),
+        false => {
+            out.push_str("export interface ErrorEnvelope {\n");
+            if !config.is_result_omitted() {
+                member(&mut out, config.result_member_name(), "null", false);
+            }
+            member(&mut out, config.error_member_name(), "ErrorBody", false);
+            out.push_str("}\n\n");
+        }
+    }
+
+    out.push_str("export interface ProblemDetails {\n");
+    member(&mut out, "type", "string", false);
+    member(&mut out, "title", "string", false);
+    member(&mut out, "status", "number", false);
+    member(&mut out, "detail", "string", false);
+    member(&mut out, "instance", "string", true);
+    member(&mut out, "code", "ErrorCode", false);
+    optional_members(&mut out);
+    out.push_str("}\n");
+
+    out
+}
+
+fn optional_members(out: &mut String) {
+    for (name, ty) in OPTIONAL_MEMBERS {
+        member(out, name, ty, true);
+    }
+    out.push_str("  [member: string]: unknown;\n");
+}
+
+fn member(out: &mut String, name: &str, ty: &str, optional: bool) {
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    let name = match is_identifier {
+        true => name.to_string(),
+        false => string_literal(name),
+    };
+    let optional = if optional { "?" } else { "" };
+
+    let _ = writeln!(out, "  {}{}: {};", name, optional, ty);
+}
+
+/// A TypeScript string literal, written as a JSON string.
+fn string_literal(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
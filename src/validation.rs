@@ -0,0 +1,109 @@
+//! Responses for failed `validator` checks: a 422 with code
+//! `VALIDATION_ERROR` and one field error per failed check.
+
+use std::{error::Error, fmt};
+
+#[cfg(feature = "axum")]
+use axum::response::{IntoResponse, Response};
+use http::StatusCode;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::{
+    ErrorResponseBuilder, ErrorResponseContext, FieldError, IntoErrorResponseContext,
+    ToErrorResponseContext,
+};
+
+impl ToErrorResponseContext for ValidationErrors {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        let mut field_errors = Vec::new();
+        collect_field_errors(self, "", &mut field_errors);
+        field_errors.sort_by(|a, b| a.field().cmp(b.field()));
+
+        ErrorResponseBuilder::new()
+            .status_code(StatusCode::UNPROCESSABLE_ENTITY)
+            .code("VALIDATION_ERROR")
+            .message("Validation failed")
+            .field_errors(field_errors)
+            .build()
+    }
+}
+
+impl IntoErrorResponseContext for ValidationErrors {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.to_response_context()
+    }
+}
+
+/// Flattens nested structs and lists into paths such as `address.city` and
+/// `items[0].quantity`. Struct-level checks are reported on the struct's own
+/// path.
+fn collect_field_errors(errors: &ValidationErrors, prefix: &str, out: &mut Vec<FieldError>) {
+    for (field, kind) in errors.errors() {
+        let path = match (prefix, *field) {
+            (prefix, "__all__") => prefix.to_string(),
+            ("", field) => field.to_string(),
+            (prefix, field) => format!("{prefix}.{field}"),
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                out.extend(errors.iter().map(|error| {
+                    let message = match &error.message {
+                        Some(message) => message.to_string(),
+                        None => format!("failed the {} validation", error.code),
+                    };
+                    FieldError::new(path.clone(), error.code.to_ascii_uppercase(), message)
+                }));
+            }
+            ValidationErrorsKind::Struct(errors) => collect_field_errors(errors, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    collect_field_errors(errors, &format!("{path}[{index}]"), out);
+                }
+            }
+        }
+    }
+}
+
+/// Wraps [`ValidationErrors`] so a handler can return them directly, e.g.
+/// `payload.validate()?` in a handler returning
+/// `Result<_, ValidationRejection>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationRejection(pub ValidationErrors);
+
+impl From<ValidationErrors> for ValidationRejection {
+    fn from(errors: ValidationErrors) -> Self {
+        Self(errors)
+    }
+}
+
+impl fmt::Display for ValidationRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for ValidationRejection {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl ToErrorResponseContext for ValidationRejection {
+    fn to_response_context(&self) -> ErrorResponseContext {
+        self.0.to_response_context()
+    }
+}
+
+impl IntoErrorResponseContext for ValidationRejection {
+    fn into_response_context(self) -> ErrorResponseContext {
+        self.0.into_response_context()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl IntoResponse for ValidationRejection {
+    fn into_response(self) -> Response {
+        self.into_response_context().into_response()
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(test)]
+#[cfg(feature = "actix")]
+mod tests {
+    use actix_web::{body::to_bytes, http::StatusCode, ResponseError};
+    use axum_error_handler::AxumErrorResponse;
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum OrderError {
+        #[error("Order {0} not found")]
+        #[response(status_code = 404, code = "ORDER_NOT_FOUND")]
+        NotFound(u32),
+    }
+
+    #[tokio::test]
+    async fn implements_response_error() {
+        let err = OrderError::NotFound(7);
+        assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+
+        let resp = err.error_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "result": null,
+                "error": {"code": "ORDER_NOT_FOUND", "message": "Order 7 not found"}
+            })
+        );
+    }
+}
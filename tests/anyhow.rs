@@ -0,0 +1,65 @@
+#[cfg(test)]
+#[cfg(feature = "anyhow")]
+mod tests {
+    use std::io;
+
+    use anyhow::Context;
+    use axum::{http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{
+        register_error_mapper, register_error_type, set_fallback_mapper, AnyhowError,
+        AxumErrorResponse, ErrorResponseContext, IntoErrorResponseContext,
+    };
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum AccountError {
+        #[error("Account is frozen")]
+        #[response(status_code = 423, code = "ACCOUNT_FROZEN")]
+        Frozen,
+    }
+
+    #[derive(Debug, Error)]
+    #[error("ledger unavailable")]
+    pub struct LedgerError(#[source] AccountError);
+
+    fn setup() {
+        register_error_type::<AccountError>();
+        register_error_mapper::<io::Error>(|_| ErrorResponseContext::service_unavailable());
+        let _ = set_fallback_mapper(|err| {
+            ErrorResponseContext::internal_server_error()
+                .override_with(|builder| builder.code("UNEXPECTED").message(err.to_string()))
+        });
+    }
+
+    fn load() -> Result<(), AnyhowError> {
+        Err(AccountError::Frozen).context("loading account 7")?;
+        Ok(())
+    }
+
+    #[test]
+    fn uses_the_registered_error_in_the_chain() {
+        setup();
+
+        let ctx = load().unwrap_err().into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::LOCKED);
+        assert_eq!(ctx.code(), "ACCOUNT_FROZEN");
+
+        let err = AnyhowError::from(LedgerError(AccountError::Frozen));
+        assert_eq!(err.into_response_context().code(), "ACCOUNT_FROZEN");
+
+        let err = AnyhowError::from(io::Error::other("disk"));
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn falls_back_for_unregistered_errors() {
+        setup();
+
+        let err = AnyhowError::from(anyhow::anyhow!("something broke"));
+        let ctx = err.into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ctx.code(), "UNEXPECTED");
+        assert_eq!(ctx.message(), "something broke");
+    }
+}
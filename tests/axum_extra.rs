@@ -0,0 +1,131 @@
+#[cfg(test)]
+#[cfg(feature = "axum-extra")]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        extract::FromRequestParts,
+        http::{header, Request, StatusCode},
+        routing::post,
+        Router,
+    };
+    use axum_error_handler::{AxumErrorResponse, IntoErrorResponseContext};
+    use axum_extra::{
+        extract::{Form, FormRejection, Query, QueryRejection},
+        headers::{authorization::Bearer, Authorization, UserAgent},
+        typed_header::TypedHeaderRejection,
+        TypedHeader,
+    };
+    use serde::Deserialize;
+    use thiserror::Error;
+    use tower::ServiceExt;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    pub struct Search {
+        limit: u32,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum SearchError {
+        #[error(transparent)]
+        #[response(nested)]
+        Header(#[from] TypedHeaderRejection),
+    }
+
+    async fn header_rejection(name: &str, value: &str) -> TypedHeaderRejection {
+        let (mut parts, _) = Request::post("/")
+            .header(name, value)
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        TypedHeader::<Authorization<Bearer>>::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn codes_typed_header_rejections() {
+        let ctx = header_rejection("x-other", "1")
+            .await
+            .into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(ctx.code(), "MISSING_HEADER");
+        assert_eq!(
+            ctx.details(),
+            Some(&serde_json::json!({ "header": "authorization" }))
+        );
+
+        let ctx = header_rejection("authorization", "Basic")
+            .await
+            .into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(ctx.code(), "INVALID_HEADER");
+        assert_eq!(
+            ctx.details(),
+            Some(&serde_json::json!({ "header": "authorization" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn codes_query_and_form_rejections() {
+        let app = Router::new().route(
+            "/search",
+            post(
+                |query: Result<Query<Search>, QueryRejection>,
+                 form: Result<Form<Search>, FormRejection>| async move {
+                    let ctx = match (query, form) {
+                        (Err(rejection), _) => rejection.into_response_context(),
+                        (_, Err(rejection)) => rejection.into_response_context(),
+                        _ => return String::new(),
+                    };
+                    format!("{} {}", ctx.status_code().as_u16(), ctx.code())
+                },
+            ),
+        );
+
+        let req = Request::post("/search?limit=x")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "400 INVALID_QUERY_STRING");
+
+        let req = Request::post("/search?limit=1")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from("limit=x"))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "400 INVALID_FORM_DATA");
+
+        let req = Request::post("/search?limit=1")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Body::from("limit=1"))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "415 MISSING_FORM_CONTENT_TYPE");
+    }
+
+    #[tokio::test]
+    async fn renders_nested_header_rejections() {
+        let app = Router::new().route(
+            "/search",
+            post(
+                |agent: Result<TypedHeader<UserAgent>, TypedHeaderRejection>| async move {
+                    agent.map(|_| StatusCode::OK).map_err(SearchError::from)
+                },
+            ),
+        );
+
+        let req = Request::post("/search").body(Body::empty()).unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "MISSING_HEADER");
+        assert_eq!(body["error"]["details"]["header"], "user-agent");
+    }
+}
@@ -0,0 +1,19 @@
+#[cfg(test)]
+#[cfg(feature = "backtrace")]
+mod tests {
+    use axum_error_handler::{AxumErrorResponse, IntoErrorResponseContext};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum DbError {
+        #[error("Database unavailable")]
+        #[response(status_code = 503, code = "DB_UNAVAILABLE")]
+        Unavailable,
+    }
+
+    #[test]
+    fn captures_backtrace() {
+        let ctx = DbError::Unavailable.into_response_context();
+        assert!(ctx.backtrace().is_some());
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use axum_error_handler::{AxumErrorResponse, CatalogEntry};
+    use thiserror::Error;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UserError {
+        /// User not found
+        #[error("User not found")]
+        #[response(status_code = 404, code = "NOT_FOUND")]
+        NotFound,
+        #[error("Upstream failed: {0}")]
+        #[response(status_from = 0, code = "UPSTREAM")]
+        Upstream(u16),
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(namespace = "ORDER")]
+    pub enum OrderError {
+        #[error("Order not found")]
+        #[response(status_code = 404, code = "NOT_FOUND", title = "Missing order")]
+        NotFound,
+        #[error("User failed")]
+        #[response(nested)]
+        User(#[from] UserError),
+        #[error("Owner failed")]
+        #[response(nested, status_code = 502, code = "OWNER_FAILED")]
+        Owner(UserError),
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum AccountError {
+        #[error("Account not found")]
+        #[response(status_code = 404, code = "NOT_FOUND")]
+        NotFound,
+    }
+
+    #[test]
+    fn lists_every_derived_enum() {
+        assert_eq!(
+            axum_error_handler::catalog(),
+            vec![
+                CatalogEntry {
+                    error: "AccountError",
+                    variant: "NotFound",
+                    status: Some(404),
+                    code: Some("NOT_FOUND".to_string()),
+                    title: None,
+                    internal: false,
+                },
+                CatalogEntry {
+                    error: "OrderError",
+                    variant: "NotFound",
+                    status: Some(404),
+                    code: Some("ORDER.NOT_FOUND".to_string()),
+                    title: Some("Missing order"),
+                    internal: false,
+                },
+                CatalogEntry {
+                    error: "OrderError",
+                    variant: "User",
+                    status: None,
+                    code: None,
+                    title: None,
+                    internal: false,
+                },
+                CatalogEntry {
+                    error: "OrderError",
+                    variant: "Owner",
+                    status: Some(502),
+                    code: Some("ORDER.OWNER_FAILED".to_string()),
+                    title: None,
+                    internal: false,
+                },
+                CatalogEntry {
+                    error: "UserError",
+                    variant: "NotFound",
+                    status: Some(404),
+                    code: Some("NOT_FOUND".to_string()),
+                    title: Some("User not found"),
+                    internal: false,
+                },
+                CatalogEntry {
+                    error: "UserError",
+                    variant: "Upstream",
+                    status: None,
+                    code: Some("UPSTREAM".to_string()),
+                    title: None,
+                    internal: false,
+                },
+            ]
+        );
+        assert_eq!(axum_error_handler::duplicate_codes(), vec!["NOT_FOUND"]);
+    }
+}
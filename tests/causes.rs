@@ -0,0 +1,81 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use std::fmt;
+
+    use axum::{body::to_bytes, response::IntoResponse};
+    use axum_error_handler::{AxumErrorResponse, IntoErrorResponseContext};
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    #[error("connection refused")]
+    pub struct ConnectError;
+
+    #[derive(Debug, Error)]
+    #[error("query failed")]
+    pub struct QueryError(#[source] ConnectError);
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum RepoError {
+        #[error("Could not load user")]
+        #[response(status_code = 500, code = "LOAD_FAILED")]
+        Load(#[from] QueryError),
+        #[error("Could not save user")]
+        #[response(status_code = 500, code = "SAVE_FAILED", internal)]
+        Save(#[source] QueryError),
+    }
+
+    /// Implements `Display` only, so it has no source chain.
+    #[derive(Debug, AxumErrorResponse)]
+    pub enum PlainError {
+        #[response(status_code = 400, code = "PLAIN")]
+        Plain,
+    }
+
+    impl fmt::Display for PlainError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("Plain")
+        }
+    }
+
+    #[tokio::test]
+    async fn renders_causes_when_enabled() {
+        axum_error_handler::set_include_causes(true).ok();
+
+        let err = RepoError::Load(QueryError(ConnectError));
+        let ctx = err.into_response_context();
+        assert_eq!(ctx.sources(), ["query failed", "connection refused"]);
+
+        let bytes = to_bytes(ctx.into_response().into_body(), 10485760)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body["error"]["causes"],
+            serde_json::json!(["query failed", "connection refused"])
+        );
+
+        let ctx = PlainError::Plain.into_response_context();
+        assert!(ctx.sources().is_empty());
+    }
+
+    #[tokio::test]
+    async fn leaves_causes_out_of_internal_errors() {
+        axum_error_handler::set_include_causes(true).ok();
+
+        let ctx = RepoError::Save(QueryError(ConnectError)).into_response_context();
+        assert_eq!(ctx.sources(), ["query failed", "connection refused"]);
+
+        let bytes = to_bytes(ctx.into_response().into_body(), 10485760)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "result": null,
+                "error": {"code": "INTERNAL_ERROR", "message": "An internal error occurred"}
+            })
+        );
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use axum::{body::to_bytes, response::IntoResponse};
+    use axum_error_handler::{AxumErrorResponse, ChainEntry, IntoErrorResponseContext};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum DbError {
+        #[error("Row not found")]
+        #[response(status_code = 404, code = "ROW_NOT_FOUND")]
+        NotFound,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum RepoError {
+        #[error("Could not load the order")]
+        #[response(nested)]
+        Db(DbError),
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum ApiError {
+        #[error("Order lookup failed")]
+        #[response(nested, code = "ORDER_LOOKUP")]
+        Orders(RepoError),
+        #[error("Bad input")]
+        #[response(status_code = 400, code = "BAD_INPUT")]
+        BadInput,
+    }
+
+    #[tokio::test]
+    async fn renders_chain_when_enabled() {
+        axum_error_handler::set_include_chain(true).unwrap();
+
+        let ctx = ApiError::Orders(RepoError::Db(DbError::NotFound)).into_response_context();
+        assert_eq!(ctx.code(), "ORDER_LOOKUP");
+        assert_eq!(
+            ctx.chain(),
+            [
+                ChainEntry::new("ORDER_LOOKUP", "Order lookup failed"),
+                ChainEntry::new("Db", "Could not load the order"),
+                ChainEntry::new("ROW_NOT_FOUND", "Row not found"),
+            ]
+        );
+
+        let bytes = to_bytes(ctx.into_response().into_body(), 10485760)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body["error"]["chain"],
+            serde_json::json!([
+                { "code": "ORDER_LOOKUP", "message": "Order lookup failed" },
+                { "code": "Db", "message": "Could not load the order" },
+                { "code": "ROW_NOT_FOUND", "message": "Row not found" },
+            ])
+        );
+
+        let bytes = to_bytes(ApiError::BadInput.into_response().into_body(), 10485760)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(body["error"].get("chain").is_none());
+    }
+}
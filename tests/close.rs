@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use axum_error_handler::{AxumErrorResponse, ErrorResponseContext, ToErrorResponseContext};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum SessionError {
+        #[error("Session expired")]
+        #[response(status_code = 401, code = "SESSION_EXPIRED")]
+        Expired,
+        #[error("Message too large")]
+        #[response(status_code = 413, code = "MESSAGE_TOO_LARGE")]
+        TooLarge,
+        #[error("{0}")]
+        #[response(status_code = 500, code = "INTERNAL")]
+        Internal(String),
+    }
+
+    #[test]
+    fn maps_statuses_to_close_codes() {
+        assert_eq!(
+            SessionError::Expired.to_response_context().close_code(),
+            1008
+        );
+        assert_eq!(
+            SessionError::TooLarge.to_response_context().close_code(),
+            1009
+        );
+        assert_eq!(
+            ErrorResponseContext::service_unavailable().close_code(),
+            1013
+        );
+        assert_eq!(
+            SessionError::Internal("boom".into())
+                .to_response_context()
+                .close_code(),
+            1011
+        );
+    }
+
+    #[test]
+    fn fits_the_reason_into_a_close_frame() {
+        let ctx = SessionError::Expired.to_response_context();
+        assert_eq!(ctx.close_reason(), "SESSION_EXPIRED: Session expired");
+
+        let ctx = SessionError::Internal("é".repeat(100)).to_response_context();
+        let reason = ctx.close_reason();
+        assert!(reason.len() <= 123);
+        assert!(reason.starts_with("INTERNAL: é"));
+    }
+
+    #[cfg(feature = "ws")]
+    #[test]
+    fn builds_axum_close_frames() {
+        let frame = SessionError::TooLarge
+            .to_response_context()
+            .into_close_frame();
+        assert_eq!(frame.code, 1009);
+        assert_eq!(frame.reason, "MESSAGE_TOO_LARGE: Message too large");
+
+        let frame = SessionError::Internal("日本".repeat(30))
+            .to_response_context()
+            .into_close_frame();
+        // "INTERNAL: " and 37 three-byte characters; a 38th would need 124.
+        assert_eq!(frame.reason.len(), 121);
+        assert!(frame.reason.starts_with("INTERNAL: 日本"));
+    }
+}
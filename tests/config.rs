@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use axum_error_handler::{AxumErrorResponse, ErrorConfig, IntoErrorResponseContext};
+    use serde_json::{json, Value};
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    #[error("connection refused")]
+    pub struct ConnectError;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum RepoError {
+        #[error("Could not reach db-primary:5432")]
+        #[response(status_code = 500, code = "LOAD_FAILED")]
+        Load(#[from] ConnectError),
+        #[error("User {0} not found")]
+        #[response(status_code = 404, code = "USER_NOT_FOUND")]
+        NotFound(u32),
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum ApiError {
+        #[error("Repository failed")]
+        #[response(nested)]
+        Repo(#[from] RepoError),
+        #[error("Shard {shard} is down")]
+        #[response(status_code = 503, code = "SHARD_DOWN")]
+        ShardDown {
+            #[response(extension)]
+            shard: String,
+            #[response(body)]
+            replicas: Vec<String>,
+        },
+        #[error("Shard {shard} is unknown")]
+        #[response(status_code = 400, code = "UNKNOWN_SHARD")]
+        UnknownShard {
+            #[response(extension)]
+            shard: String,
+        },
+    }
+
+    fn body_json(err: impl IntoErrorResponseContext) -> Value {
+        let resp = err.into_response_context().into_http_response();
+        serde_json::from_slice(resp.body()).unwrap()
+    }
+
+    #[test]
+    fn hides_server_error_messages() {
+        axum_error_handler::set_error_config(ErrorConfig {
+            expose_messages: false,
+            expose_sources: true,
+        })
+        .unwrap();
+
+        assert_eq!(
+            body_json(RepoError::Load(ConnectError)),
+            json!({
+                "result": null,
+                "error": {"code": "LOAD_FAILED", "message": "Internal Server Error"}
+            })
+        );
+        assert_eq!(
+            body_json(RepoError::NotFound(7))["error"]["message"],
+            "User 7 not found"
+        );
+
+        let ctx = RepoError::Load(ConnectError).into_response_context();
+        assert_eq!(ctx.message(), "Could not reach db-primary:5432");
+        assert_eq!(ctx.sources(), ["connection refused"]);
+
+        axum_error_handler::set_include_chain(true).unwrap();
+
+        let body = body_json(ApiError::Repo(RepoError::Load(ConnectError)));
+        assert_eq!(body["error"]["message"], "Internal Server Error");
+        assert!(body["error"].get("chain").is_none());
+        assert_eq!(
+            body_json(ApiError::Repo(RepoError::NotFound(7)))["error"]["chain"][1]["message"],
+            "User 7 not found"
+        );
+
+        let body = body_json(ApiError::ShardDown {
+            shard: "eu-1".to_string(),
+            replicas: vec!["db-eu-1b".to_string()],
+        });
+        assert_eq!(
+            body,
+            json!({
+                "result": null,
+                "error": {"code": "SHARD_DOWN", "message": "Service Unavailable"}
+            })
+        );
+        assert_eq!(
+            body_json(ApiError::UnknownShard {
+                shard: "eu-9".to_string()
+            })["error"]["shard"],
+            "eu-9"
+        );
+    }
+
+    #[test]
+    fn reads_the_environment() {
+        assert_eq!(
+            ErrorConfig::from_env(),
+            ErrorConfig {
+                expose_messages: true,
+                expose_sources: false,
+            }
+        );
+
+        std::env::set_var("AXUM_ERROR_EXPOSE_MESSAGES", "0");
+        std::env::set_var("AXUM_ERROR_EXPOSE_SOURCES", "true");
+        assert_eq!(
+            ErrorConfig::from_env(),
+            ErrorConfig {
+                expose_messages: false,
+                expose_sources: true,
+            }
+        );
+    }
+}
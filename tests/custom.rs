@@ -0,0 +1,233 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use axum::{
+        body::to_bytes,
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    };
+    use axum_error_handler::{
+        AxumErrorResponse, CustomErrorResponder, ErrorResponseContext, RequestInfo,
+    };
+    use thiserror::Error;
+
+    mod renderers {
+        use axum::response::{IntoResponse, Response};
+        use axum_error_handler::{ErrorResponseContext, RequestInfo};
+
+        pub fn render(ctx: ErrorResponseContext) -> Response {
+            (
+                ctx.status_code(),
+                format!("[{}] {}", ctx.code(), ctx.message()),
+            )
+                .into_response()
+        }
+
+        #[allow(clippy::result_large_err)]
+        pub fn render_strict(ctx: ErrorResponseContext) -> Result<Response, ErrorResponseContext> {
+            match ctx.retry_after_secs() {
+                Some(secs) => Ok((ctx.status_code(), format!("retry in {secs}s")).into_response()),
+                None => Err(ctx),
+            }
+        }
+
+        pub fn render_upload(ctx: ErrorResponseContext, err: &super::UploadError) -> Response {
+            let super::UploadError::TooLarge { limit } = err;
+            let body = format!("{}: limit is {limit} bytes", ctx.code());
+            (ctx.status_code(), body).into_response()
+        }
+
+        pub fn render_localized(
+            ctx: ErrorResponseContext,
+            request: Option<&RequestInfo>,
+        ) -> Response {
+            let locale = request
+                .and_then(|request| request.headers().get("accept-language"))
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("en");
+            let body = match locale {
+                "de" => "Seite nicht gefunden".to_string(),
+                _ => ctx.message().to_string(),
+            };
+
+            (ctx.status_code(), body).into_response()
+        }
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(custom_fn = "crate::tests::renderers::render")]
+    pub enum StringPathError {
+        #[error("Gone")]
+        #[response(status_code = 410, code = "GONE")]
+        Gone,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(custom_fn = renderers::render)]
+    pub enum BarePathError {
+        #[error("Locked")]
+        #[response(status_code = 423, code = "LOCKED")]
+        Locked,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(custom_fn_with_error = renderers::render_upload)]
+    pub enum UploadError {
+        #[error("Upload too large")]
+        #[response(status_code = 413, code = "TOO_LARGE")]
+        TooLarge { limit: u64 },
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(custom_fn = renderers::render_strict)]
+    pub enum RateError {
+        #[error("Slow down")]
+        #[response(status_code = 429, code = "SLOW_DOWN", retry_after = 5)]
+        SlowDown,
+        #[error("Quota exhausted")]
+        #[response(status_code = 429, code = "QUOTA")]
+        Quota,
+    }
+
+    pub struct Branded<const SHOW_CODE: bool>;
+
+    impl<const SHOW_CODE: bool> CustomErrorResponder for Branded<SHOW_CODE> {
+        fn respond(ctx: ErrorResponseContext) -> Response {
+            let body = match SHOW_CODE {
+                true => format!("Acme: {} ({})", ctx.message(), ctx.code()),
+                false => format!("Acme: {}", ctx.message()),
+            };
+
+            (ctx.status_code(), body).into_response()
+        }
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(responder = Branded<true>)]
+    pub enum CartError {
+        #[error("Cart is empty")]
+        #[response(status_code = 400, code = "EMPTY_CART")]
+        Empty,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(custom_fn_with_request = renderers::render_localized)]
+    pub enum PageError {
+        #[error("Page not found")]
+        #[response(status_code = 404, code = "PAGE_NOT_FOUND")]
+        NotFound,
+    }
+
+    pub struct PathAware;
+
+    impl CustomErrorResponder for PathAware {
+        fn respond(ctx: ErrorResponseContext) -> Response {
+            (ctx.status_code(), ctx.message().to_string()).into_response()
+        }
+
+        fn respond_to(ctx: ErrorResponseContext, request: Option<&RequestInfo>) -> Response {
+            match request {
+                Some(request) => {
+                    let body = format!("{} {}: {}", request.method(), request.uri(), ctx.code());
+                    (ctx.status_code(), body).into_response()
+                }
+                None => Self::respond(ctx),
+            }
+        }
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(responder = PathAware)]
+    pub enum InvoiceError {
+        #[error("Invoice not found")]
+        #[response(status_code = 404, code = "INVOICE_NOT_FOUND")]
+        NotFound,
+    }
+
+    async fn body_string(resp: axum::response::Response) -> String {
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn renders_with_custom_fn_path() {
+        let resp = StringPathError::Gone.into_response();
+        assert_eq!(resp.status(), StatusCode::GONE);
+        assert_eq!(body_string(resp).await, "[GONE] Gone");
+
+        let resp = BarePathError::Locked.into_response();
+        assert_eq!(body_string(resp).await, "[LOCKED] Locked");
+    }
+
+    #[tokio::test]
+    async fn passes_error_to_custom_fn() {
+        let resp = UploadError::TooLarge { limit: 1024 }.into_response();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body_string(resp).await, "TOO_LARGE: limit is 1024 bytes");
+    }
+
+    #[tokio::test]
+    async fn falls_back_when_custom_fn_fails() {
+        let resp = RateError::SlowDown.into_response();
+        assert_eq!(body_string(resp).await, "retry in 5s");
+
+        let resp = RateError::Quota.into_response();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            body_string(resp).await,
+            r#"{"error":{"code":"QUOTA","message":"Quota exhausted"},"result":null}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_with_responder() {
+        let resp = CartError::Empty.into_response();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_string(resp).await, "Acme: Cart is empty (EMPTY_CART)");
+    }
+
+    #[tokio::test]
+    async fn passes_request_to_custom_fn() {
+        use axum::{routing::get, Router};
+        use axum_error_handler::RequestContextLayer;
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/pages/:slug", get(|| async { PageError::NotFound }))
+            .layer(RequestContextLayer::new());
+
+        let req = axum::http::Request::get("/pages/about")
+            .header("accept-language", "de")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body_string(resp).await, "Seite nicht gefunden");
+
+        let resp = PageError::NotFound.into_response();
+        assert_eq!(body_string(resp).await, "Page not found");
+    }
+
+    #[tokio::test]
+    async fn passes_request_to_responder() {
+        use axum::{routing::get, Router};
+        use axum_error_handler::RequestContextLayer;
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/invoices/:id", get(|| async { InvoiceError::NotFound }))
+            .layer(RequestContextLayer::new());
+
+        let req = axum::http::Request::get("/invoices/7?draft=1")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(
+            body_string(resp).await,
+            "GET /invoices/7?draft=1: INVOICE_NOT_FOUND"
+        );
+
+        let resp = InvoiceError::NotFound.into_response();
+        assert_eq!(body_string(resp).await, "Invoice not found");
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+#[cfg(feature = "diesel")]
+mod tests {
+    use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{AxumErrorResponse, DieselError, IntoErrorResponseContext};
+    use diesel::result::{DatabaseErrorKind, Error as DieselResultError};
+    use thiserror::Error;
+
+    fn database_error(kind: DatabaseErrorKind) -> DieselResultError {
+        let message = "duplicate key value violates unique constraint \"users_email_key\"";
+        DieselResultError::DatabaseError(kind, Box::new(message.to_string()))
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UserError {
+        #[error(transparent)]
+        #[response(nested)]
+        Db(#[from] DieselResultError),
+    }
+
+    #[test]
+    fn maps_diesel_errors() {
+        let ctx = DieselResultError::NotFound.into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "NOT_FOUND");
+
+        let ctx = database_error(DatabaseErrorKind::UniqueViolation).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::CONFLICT);
+        assert_eq!(ctx.code(), "CONFLICT");
+
+        let ctx = database_error(DatabaseErrorKind::CheckViolation).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let ctx = database_error(DatabaseErrorKind::ClosedConnection).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn redacts_other_errors() {
+        let ctx = DieselResultError::RollbackTransaction.into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ctx.message(), "Internal Server Error");
+
+        let ctx = database_error(DatabaseErrorKind::SerializationFailure).into_response_context();
+        assert_eq!(ctx.message(), "Internal Server Error");
+        assert!(ctx.sources()[0].contains("users_email_key"));
+    }
+
+    #[tokio::test]
+    async fn renders_the_wrapper_and_nested_errors() {
+        let resp = DieselError::from(DieselResultError::NotFound).into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let err = UserError::from(database_error(DatabaseErrorKind::UniqueViolation));
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(
+            body,
+            r#"{"error":{"code":"CONFLICT","message":"Conflict"},"result":null}"#
+        );
+    }
+}
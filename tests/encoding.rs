@@ -0,0 +1,148 @@
+#[cfg(test)]
+#[cfg(feature = "xml")]
+mod xml {
+    use axum::{
+        body::{to_bytes, Body},
+        http::Request,
+        response::IntoResponse,
+        routing::get,
+        Router,
+    };
+    use axum_error_handler::{AxumErrorResponse, RequestContextLayer};
+    use thiserror::Error;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(encoding = "xml")]
+    pub enum LedgerError {
+        #[error("Entry <{0}> is locked")]
+        #[response(status_code = 409, code = "ENTRY_LOCKED")]
+        Locked(String),
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem")]
+    pub enum StockError {
+        #[error("Out of stock")]
+        #[response(status_code = 409, code = "OUT_OF_STOCK")]
+        OutOfStock {
+            #[response(extension)]
+            skus: Vec<String>,
+        },
+    }
+
+    async fn body_string(resp: axum::response::Response) -> String {
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn renders_envelope_as_xml() {
+        let resp = LedgerError::Locked("a&b".to_string()).into_response();
+        assert_eq!(resp.headers()["content-type"], "application/xml");
+
+        assert_eq!(
+            body_string(resp).await,
+            r#"<?xml version="1.0" encoding="UTF-8"?><response><error><code>ENTRY_LOCKED</code><message>Entry &lt;a&amp;b&gt; is locked</message></error><result/></response>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn negotiates_problem_xml() {
+        let app = Router::new()
+            .route(
+                "/stock",
+                get(|| async {
+                    StockError::OutOfStock {
+                        skus: vec!["A1".to_string(), "B2".to_string()],
+                    }
+                }),
+            )
+            .layer(RequestContextLayer::new());
+
+        let req = Request::get("/stock")
+            .header("accept", "application/json;q=0.5, application/problem+xml")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.headers()["content-type"], "application/problem+xml");
+
+        let body = body_string(resp).await;
+        assert!(body.contains(r#"<problem xmlns="urn:ietf:rfc:7807">"#));
+        assert!(body.contains("<skus><i>A1</i><i>B2</i></skus>"));
+        assert!(body.contains("<instance>/stock</instance>"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "msgpack")]
+mod msgpack {
+    use axum::{
+        body::{to_bytes, Body},
+        http::Request,
+        routing::get,
+        Router,
+    };
+    use axum_error_handler::{AxumErrorResponse, RequestContextLayer};
+    use thiserror::Error;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum QueueError {
+        #[error("Queue is full")]
+        #[response(status_code = 503, code = "QUEUE_FULL")]
+        Full,
+    }
+
+    #[tokio::test]
+    async fn negotiates_msgpack_envelope() {
+        let app = Router::new()
+            .route("/jobs", get(|| async { QueueError::Full }))
+            .layer(RequestContextLayer::new());
+
+        let req = Request::get("/jobs")
+            .header("accept", "application/msgpack")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.headers()["content-type"], "application/msgpack");
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let body: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "result": null,
+                "error": { "code": "QUEUE_FULL", "message": "Queue is full" },
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "cbor")]
+mod cbor {
+    use axum::{body::to_bytes, response::IntoResponse};
+    use axum_error_handler::AxumErrorResponse;
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem", encoding = "cbor")]
+    pub enum SensorError {
+        #[error("Sensor {0} is offline")]
+        #[response(status_code = 503, code = "SENSOR_OFFLINE")]
+        Offline(u32),
+    }
+
+    #[tokio::test]
+    async fn renders_cbor_problem() {
+        let resp = SensorError::Offline(7).into_response();
+        assert_eq!(resp.headers()["content-type"], "application/cbor");
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let body: serde_json::Value = ciborium::from_reader(&bytes[..]).unwrap();
+        assert_eq!(body["status"], 503);
+        assert_eq!(body["detail"], "Sensor 7 is offline");
+        assert_eq!(body["code"], "SENSOR_OFFLINE");
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use std::convert::Infallible;
+
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Request, StatusCode},
+        response::Response,
+    };
+    use axum_error_handler::{AxumErrorResponse, BoxError, ErrorHandlerLayer};
+    use thiserror::Error;
+    use tower::{service_fn, ServiceBuilder, ServiceExt};
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum GatewayError {
+        #[error("Upstream is down")]
+        #[response(status_code = 502, code = "UPSTREAM_DOWN")]
+        Down,
+    }
+
+    #[derive(Debug, Error)]
+    #[error("connection refused")]
+    pub struct ConnectError;
+
+    impl From<ConnectError> for GatewayError {
+        fn from(_: ConnectError) -> Self {
+            GatewayError::Down
+        }
+    }
+
+    async fn body_string(resp: Response) -> String {
+        let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn renders_service_errors_through_the_context() {
+        let service = ServiceBuilder::new()
+            .layer(ErrorHandlerLayer::<GatewayError>::with_error_type())
+            .service(service_fn(|_: Request<Body>| async {
+                Err::<Response, _>(ConnectError)
+            }));
+
+        let resp = service.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(
+            body_string(resp).await,
+            r#"{"error":{"code":"UPSTREAM_DOWN","message":"Upstream is down"},"result":null}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_boxed_errors_as_500() {
+        let service = ServiceBuilder::new()
+            .layer(ErrorHandlerLayer::new())
+            .service(service_fn(|_: Request<Body>| async {
+                Err::<Response, BoxError>("boom".into())
+            }));
+
+        let resp = service.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn passes_responses_through() {
+        let service = ServiceBuilder::new()
+            .layer(ErrorHandlerLayer::new())
+            .service(service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>(Response::new(Body::from("ok")))
+            }));
+
+        let resp = service.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(body_string(resp).await, "ok");
+    }
+
+    #[cfg(feature = "tower")]
+    #[tokio::test]
+    async fn renders_tower_errors() {
+        use std::time::Duration;
+
+        use axum::{routing::get, Router};
+
+        let app = Router::new()
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "done"
+                }),
+            )
+            .layer(
+                ServiceBuilder::new()
+                    .layer(ErrorHandlerLayer::new())
+                    .timeout(Duration::from_millis(10)),
+            );
+
+        let req = Request::get("/slow").body(Body::empty()).unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::REQUEST_TIMEOUT);
+        assert_eq!(
+            body_string(resp).await,
+            r#"{"error":{"code":"REQUEST_TIMEOUT","message":"Request Timeout"},"result":null}"#
+        );
+
+        let app = Router::new().route("/", get(|| async { "ok" })).layer(
+            ServiceBuilder::new()
+                .layer(ErrorHandlerLayer::new())
+                .load_shed()
+                .concurrency_limit(0),
+        );
+
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+#[cfg(feature = "eyre")]
+mod tests {
+    use axum::{http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{
+        register_error_type, AxumErrorResponse, EyreReport, IntoErrorResponseContext,
+        ResponseHandler, WithResponseMessage,
+    };
+    use eyre::WrapErr;
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum AccountError {
+        #[error("Account is frozen")]
+        #[response(status_code = 423, code = "ACCOUNT_FROZEN")]
+        Frozen,
+    }
+
+    fn setup() {
+        let _ = ResponseHandler::install();
+        register_error_type::<AccountError>();
+    }
+
+    #[test]
+    fn uses_the_registered_error_in_the_chain() {
+        setup();
+
+        let report = Err::<(), _>(AccountError::Frozen)
+            .wrap_err("loading account 7")
+            .unwrap_err();
+        let resp = EyreReport(report).into_response();
+        assert_eq!(resp.status(), StatusCode::LOCKED);
+
+        let ctx = EyreReport::from(eyre::eyre!("boom")).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ctx.sources(), ["boom"]);
+    }
+
+    #[test]
+    fn uses_the_handler_message() {
+        setup();
+
+        let result: Result<(), EyreReport> = (|| {
+            Err::<(), _>(eyre::eyre!("connection reset by peer"))
+                .response_message("Please try again later")?;
+            Ok(())
+        })();
+
+        let ctx = result.unwrap_err().into_response_context();
+        assert_eq!(ctx.code(), "INTERNAL_SERVER_ERROR");
+        assert_eq!(ctx.message(), "Please try again later");
+
+        let report = eyre::Report::new(AccountError::Frozen).response_message("Frozen");
+        let ctx = EyreReport(report).into_response_context();
+        assert_eq!(ctx.code(), "ACCOUNT_FROZEN");
+        assert_eq!(ctx.message(), "Frozen");
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{header, Request, StatusCode},
+        response::Response,
+        routing::get,
+        Router,
+    };
+    use axum_error_handler::{
+        method_not_allowed_handler, not_found_handler, ErrorFormat, NotFoundService,
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn body_json(resp: Response) -> Value {
+        let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn request(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn renders_unmatched_routes() {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .fallback(not_found_handler);
+
+        let resp = app.oneshot(request("/missing")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_json(resp).await,
+            json!({"result": null, "error": {"code": "ROUTE_NOT_FOUND", "message": "Route not found"}})
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_unmatched_routes_as_problems() {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .fallback_service(NotFoundService::new().format(ErrorFormat::Problem));
+
+        let resp = app.oneshot(request("/missing")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+        let body = body_json(resp).await;
+        assert_eq!(body["status"], 404);
+        assert_eq!(body["detail"], "Route not found");
+    }
+
+    #[tokio::test]
+    async fn renders_unmatched_methods_with_allow() {
+        let app = Router::new().route(
+            "/",
+            get(|| async { "ok" })
+                .post(|| async { "ok" })
+                .fallback(method_not_allowed_handler),
+        );
+
+        let req = Request::builder()
+            .method("DELETE")
+            .uri("/")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(resp.headers()[header::ALLOW], "GET,HEAD,POST");
+        assert_eq!(
+            body_json(resp).await,
+            json!({"result": null, "error": {"code": "METHOD_NOT_ALLOWED", "message": "Method not allowed"}})
+        );
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+#[cfg(feature = "tonic")]
+mod tests {
+    use axum_error_handler::{
+        tonic::{Code, Status},
+        AxumErrorResponse, ErrorResponseContext,
+    };
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(grpc)]
+    pub enum InventoryError {
+        #[error("Item {0} not found")]
+        #[response(status_code = 404, code = "ITEM_NOT_FOUND")]
+        NotFound(u32),
+        #[error("Warehouse unreachable")]
+        #[response(status_code = 503, code = "WAREHOUSE_DOWN")]
+        #[response(header("x-warehouse", "eu-1"))]
+        Unavailable,
+    }
+
+    #[test]
+    fn converts_into_status() {
+        let status = Status::from(InventoryError::NotFound(9));
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "Item 9 not found");
+
+        let details: serde_json::Value = serde_json::from_slice(status.details()).unwrap();
+        assert_eq!(details["code"], "ITEM_NOT_FOUND");
+
+        let status: Status = InventoryError::Unavailable.into();
+        assert_eq!(status.code(), Code::Unavailable);
+        assert_eq!(status.metadata().get("x-warehouse").unwrap(), "eu-1");
+    }
+
+    #[test]
+    fn converts_context_into_status() {
+        let status = Status::from(ErrorResponseContext::bad_request());
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(status.message(), "Bad Request");
+
+        let status: Status = ErrorResponseContext::not_found().into();
+        assert_eq!(status.code(), Code::NotFound);
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use axum::{
+        http::{HeaderValue, StatusCode},
+        response::{IntoResponse, Response},
+    };
+    use axum_error_handler::{AxumErrorResponse, ErrorResponseContext};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum FeedError {
+        #[error("Feed not found")]
+        #[response(status_code = 404, code = "FEED_NOT_FOUND")]
+        NotFound,
+        #[error("Feed unavailable")]
+        #[response(status_code = 503, code = "FEED_DOWN")]
+        Unavailable,
+    }
+
+    fn hook(ctx: &ErrorResponseContext, mut response: Response) -> Response {
+        if let Ok(code) = HeaderValue::from_str(ctx.code()) {
+            response.headers_mut().insert("x-error-code", code);
+        }
+        if ctx.status_code().is_server_error() {
+            *response.status_mut() = StatusCode::BAD_GATEWAY;
+        }
+        response
+    }
+
+    #[test]
+    fn post_processes_rendered_responses() {
+        axum_error_handler::set_response_hook(hook).unwrap();
+
+        let resp = FeedError::NotFound.into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(resp.headers()["x-error-code"], "FEED_NOT_FOUND");
+        assert_eq!(resp.headers()["content-type"], "application/json");
+
+        let resp = FeedError::Unavailable.into_response();
+        assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(resp.headers()["x-error-code"], "FEED_DOWN");
+
+        assert!(axum_error_handler::set_response_hook(hook).is_err());
+    }
+}
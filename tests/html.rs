@@ -0,0 +1,77 @@
+#[cfg(test)]
+#[cfg(feature = "html")]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::Request,
+        routing::get,
+        Router,
+    };
+    use axum_error_handler::{
+        AxumErrorResponse, HtmlPage, RequestContextLayer, ToErrorResponseContext,
+    };
+    use thiserror::Error;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum PageError {
+        /// Page not found
+        #[error("No page at <{0}>")]
+        #[response(status_code = 404, code = "PAGE_NOT_FOUND")]
+        NotFound(String),
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/docs",
+                get(|| async { PageError::NotFound("/docs".to_string()) }),
+            )
+            .layer(RequestContextLayer::new())
+    }
+
+    #[tokio::test]
+    async fn renders_page_for_browsers() {
+        let req = Request::get("/docs")
+            .header("accept", "text/html,application/xhtml+xml,*/*;q=0.8")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404);
+        assert_eq!(resp.headers()["content-type"], "text/html; charset=utf-8");
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let page = String::from_utf8(bytes.to_vec()).unwrap();
+        assert!(page.contains("<h1>Page not found</h1>"));
+        assert!(page.contains("No page at &lt;/docs&gt;"));
+    }
+
+    #[tokio::test]
+    async fn keeps_json_for_api_clients() {
+        let req = Request::get("/docs")
+            .header("accept", "application/json")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app().oneshot(req).await.unwrap();
+        assert_eq!(resp.headers()["content-type"], "application/json");
+    }
+
+    #[test]
+    fn describes_the_page_for_templates() {
+        let ctx = PageError::NotFound("/docs".to_string())
+            .to_response_context()
+            .override_with(|builder| builder.extension_member("path", "/docs"));
+
+        assert_eq!(
+            serde_json::to_value(HtmlPage::new(&ctx)).unwrap(),
+            serde_json::json!({
+                "status": 404,
+                "title": "Page not found",
+                "code": "PAGE_NOT_FOUND",
+                "message": "No page at </docs>",
+                "help_url": null,
+                "extensions": {"path": "/docs"}
+            })
+        );
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum_error_handler::{AxumErrorResponse, ErrorResponseContext, IntoErrorResponseContext};
+    use http::{header, StatusCode};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum JobError {
+        #[error("Job {0} not found")]
+        #[response(status_code = 404, code = "JOB_NOT_FOUND")]
+        NotFound(u32),
+    }
+
+    #[test]
+    fn renders_into_http_responses() {
+        let resp = JobError::NotFound(3)
+            .into_response_context()
+            .into_http_response();
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(
+            resp.body().as_ref(),
+            br#"{"error":{"code":"JOB_NOT_FOUND","message":"Job 3 not found"},"result":null}"#
+        );
+    }
+
+    #[test]
+    fn keeps_headers() {
+        let resp = ErrorResponseContext::service_unavailable()
+            .override_with(|builder| builder.retry_after(Duration::from_secs(30)))
+            .into_http_response();
+
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "30");
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+#[cfg(feature = "i18n")]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::Request,
+        routing::get,
+        Router,
+    };
+    use axum_error_handler::{
+        fluent_bundle::{concurrent::FluentBundle, FluentResource},
+        AxumErrorResponse, FluentResolver, RequestContextLayer,
+    };
+    use thiserror::Error;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum AccountError {
+        #[error("User {user_id} not found")]
+        #[response(
+            status_code = 404,
+            code = "USER_NOT_FOUND",
+            message_key = "user-not-found"
+        )]
+        NotFound {
+            #[response(message_arg)]
+            user_id: u64,
+        },
+        #[error("Account locked")]
+        #[response(status_code = 423, code = "LOCKED", message_key = "account-locked")]
+        Locked,
+    }
+
+    fn bundle(language: &str, source: &str) -> FluentBundle<FluentResource> {
+        let mut bundle = FluentBundle::new_concurrent(vec![language.parse().unwrap()]);
+        bundle
+            .add_resource(FluentResource::try_new(source.to_string()).unwrap())
+            .unwrap();
+        bundle
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/users",
+                get(|| async { AccountError::NotFound { user_id: 7 } }),
+            )
+            .route("/locked", get(|| async { AccountError::Locked }))
+            .layer(RequestContextLayer::new())
+    }
+
+    async fn message(uri: &str, accept_language: Option<&str>) -> String {
+        let resolver = FluentResolver::new("en-US".parse().unwrap())
+            .bundle(bundle(
+                "en-US",
+                "user-not-found = No user with id { $user_id }",
+            ))
+            .bundle(bundle(
+                "de",
+                "user-not-found = Kein Benutzer mit der ID { $user_id }",
+            ));
+        axum_error_handler::set_message_resolver(resolver).ok();
+
+        let mut req = Request::get(uri);
+        if let Some(accept_language) = accept_language {
+            req = req.header("accept-language", accept_language);
+        }
+        let resp = app()
+            .oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        body["error"]["message"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn resolves_the_preferred_language() {
+        assert_eq!(
+            message("/users", Some("de-CH, de;q=0.9, en;q=0.8")).await,
+            "Kein Benutzer mit der ID 7"
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_default_language() {
+        assert_eq!(message("/users", Some("fr")).await, "No user with id 7");
+        assert_eq!(message("/users", None).await, "No user with id 7");
+    }
+
+    #[tokio::test]
+    async fn keeps_messages_without_a_translation() {
+        assert_eq!(message("/locked", Some("de")).await, "Account locked");
+    }
+}
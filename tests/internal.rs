@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+    use axum_error_handler::{
+        AxumErrorResponse, ErrorFormat, ErrorResponseContext, IntoErrorResponseContext,
+    };
+    use serde_json::{json, Value};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum VaultError {
+        #[error("Key {0} rejected by hsm-2")]
+        #[response(status_code = 500, code = "HSM_REJECTED", internal)]
+        Rejected(u32),
+        #[error("Secret not found")]
+        #[response(status_code = 404, code = "SECRET_NOT_FOUND")]
+        NotFound,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum ApiError {
+        #[error("Vault failed")]
+        #[response(nested, internal)]
+        Vault(#[from] VaultError),
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(type_base = "https://errors.example.com/")]
+    pub enum LedgerError {
+        /// Ledger shard rejected the write
+        #[error("Shard {shard} rejected entry {entry}")]
+        #[response(status_code = 500, code = "SHARD_REJECTED", internal)]
+        #[response(help = "https://wiki.internal/ledger", message_key = "ledger.rejected")]
+        Rejected {
+            #[response(extension)]
+            shard: String,
+            #[response(message_arg)]
+            entry: u64,
+            #[response(body)]
+            replicas: Vec<String>,
+        },
+        #[error("Ledger failed")]
+        #[response(nested, code = "LEDGER_FAILED", internal)]
+        Vault(#[from] VaultError),
+    }
+
+    fn rejected() -> LedgerError {
+        LedgerError::Rejected {
+            shard: "eu-1".to_string(),
+            entry: 42,
+            replicas: vec!["db-eu-1b".to_string()],
+        }
+    }
+
+    fn body_json(err: impl IntoErrorResponseContext) -> Value {
+        let resp = err.into_response_context().into_http_response();
+        serde_json::from_slice(resp.body()).unwrap()
+    }
+
+    #[test]
+    fn redacts_internal_variants() {
+        assert_eq!(
+            body_json(VaultError::Rejected(7)),
+            json!({
+                "result": null,
+                "error": {"code": "INTERNAL_ERROR", "message": "An internal error occurred"}
+            })
+        );
+        assert_eq!(
+            body_json(VaultError::NotFound)["error"]["code"],
+            "SECRET_NOT_FOUND"
+        );
+
+        let ctx = VaultError::Rejected(7).into_response_context();
+        assert!(ctx.is_internal());
+        assert_eq!(ctx.code(), "HSM_REJECTED");
+        assert_eq!(ctx.message(), "Key 7 rejected by hsm-2");
+    }
+
+    #[test]
+    fn redacts_nested_variants() {
+        let ctx = ApiError::Vault(VaultError::NotFound).into_response_context();
+        assert!(ctx.is_internal());
+        assert_eq!(ctx.status_code(), 404);
+        assert_eq!(body_json(ctx)["error"]["code"], "INTERNAL_ERROR");
+    }
+
+    #[test]
+    fn keeps_the_flag_when_merging_and_serializing() {
+        let inner = ErrorResponseContext::builder().internal().build();
+        let ctx = ErrorResponseContext::not_found().merge(inner);
+        assert!(ctx.is_internal());
+
+        let json = serde_json::to_value(&ctx).unwrap();
+        assert_eq!(json["internal"], true);
+        let ctx: ErrorResponseContext = serde_json::from_value(json).unwrap();
+        assert!(ctx.is_internal());
+    }
+
+    #[test]
+    fn leaves_variant_members_out_of_every_format() {
+        axum_error_handler::set_include_chain(true).unwrap();
+
+        let ctx = rejected()
+            .into_response_context()
+            .override_with(|builder| builder.field_error("entry", "DUPLICATE", "seen twice"));
+        assert_eq!(ctx.title(), Some("Ledger shard rejected the write"));
+        assert_eq!(
+            body_json(ctx),
+            json!({
+                "result": null,
+                "error": {"code": "INTERNAL_ERROR", "message": "An internal error occurred"}
+            })
+        );
+
+        let ctx = rejected()
+            .into_response_context()
+            .override_with(|builder| builder.format(ErrorFormat::Problem));
+        assert_eq!(
+            body_json(ctx),
+            json!({
+                "type": "about:blank",
+                "title": "Internal Server Error",
+                "status": 500,
+                "detail": "An internal error occurred",
+                "code": "INTERNAL_ERROR"
+            })
+        );
+
+        let ctx = rejected()
+            .into_response_context()
+            .override_with(|builder| builder.format(ErrorFormat::GraphQl));
+        assert_eq!(
+            body_json(ctx),
+            json!({
+                "errors": [{
+                    "message": "An internal error occurred",
+                    "extensions": {"code": "INTERNAL_ERROR"}
+                }],
+                "data": null
+            })
+        );
+
+        #[cfg(feature = "jsonapi")]
+        {
+            let ctx = rejected()
+                .into_response_context()
+                .override_with(|builder| builder.format(ErrorFormat::JsonApi));
+            assert_eq!(
+                body_json(ctx),
+                json!({
+                    "errors": [{
+                        "status": "500",
+                        "code": "INTERNAL_ERROR",
+                        "title": "Internal Server Error",
+                        "detail": "An internal error occurred"
+                    }]
+                })
+            );
+        }
+
+        let ctx = LedgerError::Vault(VaultError::NotFound).into_response_context();
+        assert_eq!(ctx.chain().len(), 2);
+        assert_eq!(
+            body_json(ctx),
+            json!({
+                "result": null,
+                "error": {"code": "INTERNAL_ERROR", "message": "An internal error occurred"}
+            })
+        );
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+#[cfg(feature = "jsonapi")]
+mod tests {
+    use axum::{body::to_bytes, response::IntoResponse};
+    use axum_error_handler::AxumErrorResponse;
+    use serde::Serialize;
+    use thiserror::Error;
+
+    #[derive(Debug, Serialize)]
+    pub struct Source {
+        pointer: &'static str,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "jsonapi")]
+    pub enum ArticleError {
+        /// Invalid attribute
+        #[error("Title must contain at least three characters.")]
+        #[response(status_code = 422, code = "TITLE_TOO_SHORT")]
+        #[response(help = "https://docs.example.com/errors/TITLE_TOO_SHORT")]
+        TitleTooShort {
+            #[response(extension = "source")]
+            pointer: Source,
+            #[response(extension)]
+            min_length: usize,
+        },
+    }
+
+    #[tokio::test]
+    async fn renders_jsonapi_errors() {
+        let err = ArticleError::TitleTooShort {
+            pointer: Source {
+                pointer: "/data/attributes/title",
+            },
+            min_length: 3,
+        };
+
+        let resp = err.into_response();
+        assert_eq!(resp.status(), 422);
+        assert_eq!(resp.headers()["content-type"], "application/vnd.api+json");
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [{
+                    "status": "422",
+                    "code": "TITLE_TOO_SHORT",
+                    "title": "Invalid attribute",
+                    "detail": "Title must contain at least three characters.",
+                    "links": { "about": "https://docs.example.com/errors/TITLE_TOO_SHORT" },
+                    "source": { "pointer": "/data/attributes/title" },
+                    "meta": { "min_length": 3 },
+                }]
+            })
+        );
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+#[cfg(feature = "jsonwebtoken")]
+mod tests {
+    use axum::{
+        http::{header, HeaderValue, StatusCode},
+        response::IntoResponse,
+    };
+    use axum_error_handler::{set_www_authenticate, AxumErrorResponse, IntoErrorResponseContext};
+    use jsonwebtoken::errors::{Error as JwtError, ErrorKind};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum AuthError {
+        #[error(transparent)]
+        #[response(nested)]
+        Token(#[from] JwtError),
+    }
+
+    fn setup() {
+        let challenge = HeaderValue::from_static(r#"Bearer realm="api", error="invalid_token""#);
+        let _ = set_www_authenticate(challenge);
+    }
+
+    #[test]
+    fn maps_token_errors_to_401() {
+        setup();
+
+        let ctx = JwtError::from(ErrorKind::ExpiredSignature).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNAUTHORIZED);
+        assert_eq!(ctx.code(), "TOKEN_EXPIRED");
+
+        let ctx = JwtError::from(ErrorKind::InvalidSignature).into_response_context();
+        assert_eq!(ctx.code(), "TOKEN_INVALID");
+
+        let ctx = JwtError::from(ErrorKind::InvalidIssuer).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNAUTHORIZED);
+        assert_eq!(ctx.code(), "UNAUTHORIZED");
+        assert_eq!(ctx.sources(), ["InvalidIssuer"]);
+    }
+
+    #[test]
+    fn sends_the_challenge() {
+        setup();
+
+        let err = AuthError::from(JwtError::from(ErrorKind::ExpiredSignature));
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            resp.headers()[header::WWW_AUTHENTICATE],
+            r#"Bearer realm="api", error="invalid_token""#
+        );
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+#[cfg(feature = "tracing")]
+mod tests {
+    use std::{
+        fmt,
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use axum::response::IntoResponse;
+    use axum_error_handler::{AxumErrorResponse, LogSampling};
+    use thiserror::Error;
+    use tracing::field::Field;
+    use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum InputError {
+        #[error("Malformed body")]
+        #[response(status_code = 400, code = "MALFORMED")]
+        Malformed,
+        #[error("Missing field")]
+        #[response(status_code = 400, code = "MISSING_FIELD")]
+        Missing,
+    }
+
+    /// The fields, formatted as `name=value`, of each event.
+    #[derive(Clone, Default)]
+    struct Events(Arc<Mutex<Vec<Vec<String>>>>);
+
+    struct FieldVisitor<'a>(&'a mut Vec<String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    impl<S: tracing::Subscriber> Layer<S> for Events {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            let mut fields = Vec::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.0.lock().unwrap().push(fields);
+        }
+    }
+
+    #[test]
+    fn drops_repeated_events_and_reports_them() {
+        axum_error_handler::set_log_sampling(LogSampling {
+            max_events: 2,
+            window: Duration::from_millis(100),
+        })
+        .unwrap();
+
+        let events = Events::default();
+        let subscriber = tracing_subscriber::registry().with(events.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..5 {
+                let _ = InputError::Malformed.into_response();
+            }
+            let _ = InputError::Missing.into_response();
+
+            thread::sleep(Duration::from_millis(150));
+            let _ = InputError::Malformed.into_response();
+
+            // A burst followed by silence is reported when flushed.
+            thread::sleep(Duration::from_millis(150));
+            for _ in 0..4 {
+                let _ = InputError::Missing.into_response();
+            }
+            axum_error_handler::flush_log_sampling();
+            thread::sleep(Duration::from_millis(150));
+            axum_error_handler::flush_log_sampling();
+        });
+
+        let events = events.0.lock().unwrap();
+        let messages = events
+            .iter()
+            .map(|fields| (fields[0].as_str(), fields[1].as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                ("message=error response", "error.code=MALFORMED"),
+                ("message=error response", "error.code=MALFORMED"),
+                ("message=error response", "error.code=MISSING_FIELD"),
+                ("message=error responses suppressed", "error.code=MALFORMED"),
+                ("message=error response", "error.code=MALFORMED"),
+                ("message=error response", "error.code=MISSING_FIELD"),
+                ("message=error response", "error.code=MISSING_FIELD"),
+                (
+                    "message=error responses suppressed",
+                    "error.code=MISSING_FIELD"
+                ),
+            ]
+        );
+        assert!(events[3].contains(&"suppressed=3".to_string()));
+        assert!(events[7].contains(&"suppressed=2".to_string()));
+    }
+}
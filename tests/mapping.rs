@@ -0,0 +1,70 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use std::io;
+
+    use axum::http::StatusCode;
+    use axum_error_handler::{
+        register_error_mapper, AxumErrorResponse, ErrorResponseContext, IntoErrorResponseContext,
+    };
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum LockError {
+        #[error("Lock held")]
+        #[response(status_code = 423, code = "LOCK_HELD")]
+        Held,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum StorageError {
+        #[error(transparent)]
+        #[response(nested_or_map)]
+        Io(io::Error),
+        #[error(transparent)]
+        #[response(nested_or_map, code_prefix = "FMT_")]
+        Fmt(std::fmt::Error),
+        #[error(transparent)]
+        #[response(nested_or_map)]
+        Lock(LockError),
+    }
+
+    fn map_io(err: &(dyn std::error::Error + 'static)) -> ErrorResponseContext {
+        match err.downcast_ref::<io::Error>().map(io::Error::kind) {
+            Some(io::ErrorKind::NotFound) => ErrorResponseContext::not_found(),
+            _ => ErrorResponseContext::service_unavailable(),
+        }
+    }
+
+    #[test]
+    fn maps_registered_errors() {
+        assert!(register_error_mapper::<io::Error>(map_io).is_none());
+
+        let err = io::Error::new(io::ErrorKind::NotFound, "missing");
+        let ctx = StorageError::Io(err).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "NOT_FOUND");
+
+        let err = io::Error::other("disk full");
+        let ctx = StorageError::Io(err).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn falls_back_to_internal_server_error() {
+        let ctx = StorageError::Fmt(std::fmt::Error).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ctx.code(), "FMT_INTERNAL_SERVER_ERROR");
+        assert_eq!(
+            ctx.sources(),
+            ["an error occurred when formatting an argument"]
+        );
+    }
+
+    #[test]
+    fn prefers_inner_context() {
+        let ctx = StorageError::Lock(LockError::Held).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::LOCKED);
+        assert_eq!(ctx.code(), "LOCK_HELD");
+    }
+}
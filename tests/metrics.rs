@@ -0,0 +1,61 @@
+#[cfg(test)]
+#[cfg(feature = "metrics")]
+mod tests {
+    use axum::response::IntoResponse;
+    use axum_error_handler::AxumErrorResponse;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum FeedError {
+        #[error("Feed not found")]
+        #[response(status_code = 404, code = "FEED_NOT_FOUND")]
+        NotFound,
+        #[error("Database failed")]
+        #[response(status_code = 500, code = "FEED_DB", internal)]
+        Database,
+    }
+
+    #[test]
+    fn counts_responses_per_code_and_status() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        metrics::with_local_recorder(&recorder, || {
+            let _ = FeedError::NotFound.into_response();
+            let _ = FeedError::NotFound.into_response();
+            let _ = FeedError::Database.into_response();
+        });
+
+        let mut counters = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let (name, labels) = key.key().clone().into_parts();
+                let labels = labels
+                    .iter()
+                    .map(|label| format!("{}={}", label.key(), label.value()))
+                    .collect::<Vec<_>>();
+                (name.as_str().to_string(), labels, value)
+            })
+            .collect::<Vec<_>>();
+        counters.sort_by(|a, b| a.1.cmp(&b.1));
+
+        assert_eq!(
+            counters,
+            vec![
+                (
+                    "error_responses_total".to_string(),
+                    vec!["code=FEED_DB".to_string(), "status=500".to_string()],
+                    DebugValue::Counter(1),
+                ),
+                (
+                    "error_responses_total".to_string(),
+                    vec!["code=FEED_NOT_FOUND".to_string(), "status=404".to_string()],
+                    DebugValue::Counter(2),
+                ),
+            ]
+        );
+    }
+}
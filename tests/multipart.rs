@@ -0,0 +1,82 @@
+#[cfg(test)]
+#[cfg(feature = "multipart")]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        extract::{
+            multipart::{MultipartError, MultipartRejection},
+            DefaultBodyLimit, Multipart,
+        },
+        http::{header, Request, StatusCode},
+        routing::post,
+        Router,
+    };
+    use axum_error_handler::AxumErrorResponse;
+    use thiserror::Error;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UploadError {
+        #[error(transparent)]
+        #[response(nested)]
+        Multipart(#[from] MultipartError),
+        #[error(transparent)]
+        #[response(nested)]
+        Rejection(#[from] MultipartRejection),
+    }
+
+    async fn upload(
+        multipart: Result<Multipart, MultipartRejection>,
+    ) -> Result<StatusCode, UploadError> {
+        let mut multipart = multipart?;
+        while let Some(field) = multipart.next_field().await? {
+            field.bytes().await?;
+        }
+        Ok(StatusCode::CREATED)
+    }
+
+    async fn send(content_type: &str, body: String) -> (StatusCode, serde_json::Value) {
+        let app = Router::new()
+            .route("/upload", post(upload))
+            .layer(DefaultBodyLimit::max(64));
+
+        let req = Request::post("/upload")
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        let status = resp.status();
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    fn form(data: &str) -> String {
+        format!(
+            "--X\r\nContent-Disposition: form-data; name=\"file\"\r\n\r\n{}\r\n--X--\r\n",
+            data
+        )
+    }
+
+    #[tokio::test]
+    async fn maps_malformed_bodies_to_400() {
+        let (status, body) = send("multipart/form-data; boundary=X", "--X\r\nbroken".into()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "MULTIPART_INVALID");
+        assert_eq!(body["error"]["message"], "Invalid multipart body");
+
+        let (status, body) = send("multipart/form-data", form("a")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "MULTIPART_INVALID");
+    }
+
+    #[tokio::test]
+    async fn maps_oversized_bodies_to_413() {
+        let (status, body) = send("multipart/form-data; boundary=X", form(&"a".repeat(128))).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["error"]["code"], "PAYLOAD_TOO_LARGE");
+
+        let (status, _) = send("multipart/form-data; boundary=X", form("a")).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use axum_error_handler::{AxumErrorResponse, IntoErrorResponseContext, ToErrorResponseContext};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(namespace = "BILLING")]
+    pub enum BillingError {
+        #[error("Invoice not found")]
+        #[response(status_code = 404, code = "NOT_FOUND")]
+        NotFound,
+        #[error("Card declined")]
+        #[response(status_code = 402)]
+        CardDeclined,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(namespace = "CHECKOUT")]
+    pub enum CheckoutError {
+        #[error("Billing failed")]
+        #[response(nested)]
+        Billing(#[from] BillingError),
+        #[error("Payment failed")]
+        #[response(nested, status_code = 502, code = "PAYMENT_FAILED")]
+        Payment(BillingError),
+    }
+
+    #[test]
+    fn prefixes_codes() {
+        assert_eq!(
+            BillingError::NotFound.to_response_context().code(),
+            "BILLING.NOT_FOUND"
+        );
+        assert_eq!(
+            BillingError::CardDeclined.to_response_context().code(),
+            "BILLING.CardDeclined"
+        );
+    }
+
+    #[test]
+    fn keeps_the_inner_namespace_of_nested_errors() {
+        let ctx = CheckoutError::Billing(BillingError::NotFound).into_response_context();
+        assert_eq!(ctx.code(), "BILLING.NOT_FOUND");
+        assert_eq!(ctx.chain()[0].code(), "CHECKOUT.Billing");
+
+        let ctx = CheckoutError::Payment(BillingError::CardDeclined).into_response_context();
+        assert_eq!(ctx.code(), "CHECKOUT.PAYMENT_FAILED");
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{
+        AxumErrorResponse, ErrorResponseContext, IntoErrorResponseContext, ToErrorResponseContext,
+    };
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum AuthError {
+        #[error("User not found")]
+        #[response(status_code = 404, code = "NOT_FOUND")]
+        NotFound,
+        #[error("Session expired")]
+        #[response(status_code = 401, code = "SESSION_EXPIRED")]
+        Expired,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum ApiError {
+        #[error(transparent)]
+        #[response(nested, code_prefix = "AUTH_")]
+        Auth(AuthError),
+        #[error(transparent)]
+        #[response(nested)]
+        Plain(AuthError),
+        #[error(transparent)]
+        #[response(nested, status_code = 502)]
+        Upstream(AuthError),
+        #[error(transparent)]
+        #[response(nested, status_code = 502, code = "UPSTREAM_AUTH")]
+        Masked(AuthError),
+        #[error("Maintenance")]
+        #[response(status_code = 503, code = "MAINTENANCE")]
+        Maintenance,
+    }
+
+    fn hide_message(ctx: ErrorResponseContext) -> ErrorResponseContext {
+        ctx.override_with(|builder| builder.message("Authentication failed"))
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum GatewayError {
+        #[error(transparent)]
+        #[response(nested, map = hide_message, code_prefix = "AUTH_")]
+        Auth(AuthError),
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum RequestError {
+        #[error("Request {request_id} failed")]
+        #[response(nested = "source")]
+        Wrapped {
+            source: AuthError,
+            request_id: String,
+        },
+        #[error(transparent)]
+        #[response(nested)]
+        Single { inner: AuthError },
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum SessionError {
+        #[error("No session")]
+        #[response(nested, fallback_status = 401, fallback_code = "NO_SESSION")]
+        Maybe(Option<AuthError>),
+        #[error("Lookup failed")]
+        #[response(nested_or_map)]
+        Lookup(Option<std::io::Error>),
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UpstreamError {
+        #[error("Upstream {1} failed: {0}")]
+        #[response(nested, nested_field = 0)]
+        Upstream(AuthError, String, std::time::Instant),
+    }
+
+    static EXPIRED: AuthError = AuthError::Expired;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum SharedError {
+        #[error(transparent)]
+        #[response(nested, code_prefix = "AUTH_")]
+        Shared(Arc<AuthError>),
+        #[error(transparent)]
+        #[response(nested)]
+        Static(&'static AuthError),
+        #[error(transparent)]
+        #[response(nested_or_map)]
+        Io(std::io::Error),
+    }
+
+    #[test]
+    fn takes_context_from_inner_error() {
+        let ctx = ApiError::Plain(AuthError::Expired).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNAUTHORIZED);
+        assert_eq!(ctx.code(), "SESSION_EXPIRED");
+        assert_eq!(ctx.message(), "Session expired");
+
+        let ctx = ApiError::Maintenance.into_response_context();
+        assert_eq!(ctx.code(), "MAINTENANCE");
+    }
+
+    #[test]
+    fn prefixes_nested_codes() {
+        let ctx = ApiError::Auth(AuthError::NotFound).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "AUTH_NOT_FOUND");
+        assert_eq!(ctx.message(), "User not found");
+
+        let resp = ApiError::Auth(AuthError::Expired).into_response();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn overrides_nested_status_and_code() {
+        let ctx = ApiError::Upstream(AuthError::NotFound).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::BAD_GATEWAY);
+        assert_eq!(ctx.code(), "NOT_FOUND");
+        assert_eq!(ctx.message(), "User not found");
+
+        let ctx = ApiError::Masked(AuthError::Expired).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::BAD_GATEWAY);
+        assert_eq!(ctx.code(), "UPSTREAM_AUTH");
+        assert_eq!(ctx.message(), "Session expired");
+    }
+
+    #[test]
+    fn nests_shared_errors() {
+        let shared = Arc::new(AuthError::NotFound);
+        let ctx = SharedError::Shared(shared.clone()).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "AUTH_NOT_FOUND");
+        assert_eq!(shared.into_response_context().code(), "NOT_FOUND");
+
+        let ctx = SharedError::Static(&EXPIRED).into_response_context();
+        assert_eq!(ctx.code(), "SESSION_EXPIRED");
+    }
+
+    #[test]
+    fn borrows_nested_errors() {
+        let err = SharedError::Shared(Arc::new(AuthError::Expired));
+        assert_eq!(err.to_response_context().code(), "AUTH_SESSION_EXPIRED");
+        assert_eq!(err.into_response_context().code(), "AUTH_SESSION_EXPIRED");
+
+        let err = SharedError::Io(std::io::Error::other("disk full"));
+        let ctx = err.to_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ctx.sources(), ["disk full"]);
+    }
+
+    #[test]
+    fn maps_nested_contexts() {
+        let err = GatewayError::Auth(AuthError::Expired);
+        let ctx = err.to_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNAUTHORIZED);
+        assert_eq!(ctx.code(), "AUTH_SESSION_EXPIRED");
+        assert_eq!(ctx.message(), "Authentication failed");
+        assert_eq!(err.into_response_context(), ctx);
+    }
+
+    #[test]
+    fn nests_named_fields() {
+        let err = RequestError::Wrapped {
+            source: AuthError::NotFound,
+            request_id: "req-1".to_string(),
+        };
+        let ctx = err.to_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "NOT_FOUND");
+        assert_eq!(ctx.chain()[0].message(), "Request req-1 failed");
+        assert_eq!(err.into_response_context(), ctx);
+
+        let err = RequestError::Single {
+            inner: AuthError::Expired,
+        };
+        assert_eq!(err.into_response_context().code(), "SESSION_EXPIRED");
+    }
+
+    #[test]
+    fn nests_optional_errors() {
+        let err = SessionError::Maybe(Some(AuthError::Expired));
+        let ctx = err.to_response_context();
+        assert_eq!(ctx.code(), "SESSION_EXPIRED");
+        assert_eq!(err.into_response_context(), ctx);
+
+        let err = SessionError::Maybe(None);
+        let ctx = err.to_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNAUTHORIZED);
+        assert_eq!(ctx.code(), "NO_SESSION");
+        assert_eq!(ctx.message(), "No session");
+        assert_eq!(err.into_response_context(), ctx);
+
+        let ctx = SessionError::Lookup(None).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ctx.code(), "Lookup");
+        assert_eq!(ctx.message(), "Lookup failed");
+    }
+
+    #[test]
+    fn selects_nested_tuple_field() {
+        let err = UpstreamError::Upstream(
+            AuthError::NotFound,
+            "accounts".to_string(),
+            std::time::Instant::now(),
+        );
+        let ctx = err.to_response_context();
+        assert_eq!(ctx.code(), "NOT_FOUND");
+        assert_eq!(
+            ctx.chain()[0].message(),
+            "Upstream accounts failed: User not found"
+        );
+        assert_eq!(err.into_response_context(), ctx);
+    }
+}
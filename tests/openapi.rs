@@ -0,0 +1,117 @@
+#[cfg(test)]
+#[cfg(feature = "utoipa")]
+mod tests {
+    use axum_error_handler::{
+        utoipa::{IntoResponses, PartialSchema, ToSchema},
+        AxumErrorResponse,
+    };
+    use serde_json::{json, Value};
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    #[error("disk full")]
+    pub struct StorageError;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum OrderError {
+        #[error("Order not found")]
+        #[response(status_code = 404, code = "ORDER_NOT_FOUND")]
+        NotFound,
+        /// The order was already shipped
+        #[error("Order {0} already shipped")]
+        #[response(status_code = 409, code = "ALREADY_SHIPPED")]
+        Shipped(u64),
+        #[error("Order is locked")]
+        #[response(status_code = 409, code = "LOCKED")]
+        Locked,
+        #[error("Status {0}")]
+        #[response(status_from = 0)]
+        Dynamic(u16),
+        #[error(transparent)]
+        #[response(nested)]
+        Inner(InnerError),
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem")]
+    pub enum InnerError {
+        #[error("Storage failed")]
+        #[response(status_code = 507, code = "STORAGE")]
+        Storage(#[source] StorageError),
+    }
+
+    fn responses<T: IntoResponses>() -> Value {
+        serde_json::to_value(T::responses()).unwrap()
+    }
+
+    #[test]
+    fn documents_each_status() {
+        let responses = responses::<OrderError>();
+        let statuses = responses.as_object().unwrap().keys().collect::<Vec<_>>();
+        assert_eq!(statuses, ["404", "409"]);
+
+        let not_found = &responses["404"];
+        assert_eq!(not_found["description"], "Order not found");
+
+        let content = &not_found["content"]["application/json"];
+        assert_eq!(
+            content["examples"]["ORDER_NOT_FOUND"]["value"],
+            json!({"result": null, "error": {"code": "ORDER_NOT_FOUND", "message": "Order not found"}})
+        );
+
+        let error = &content["schema"]["properties"]["error"];
+        assert_eq!(
+            error["properties"]["code"]["enum"],
+            json!(["ORDER_NOT_FOUND"])
+        );
+        assert_eq!(error["properties"]["message"]["type"], "string");
+        assert_eq!(content["schema"]["properties"]["result"]["type"], "null");
+    }
+
+    #[test]
+    fn groups_variants_sharing_a_status() {
+        let responses = responses::<OrderError>();
+        let conflict = &responses["409"];
+        assert_eq!(conflict["description"], "Conflict");
+
+        let content = &conflict["content"]["application/json"];
+        assert_eq!(
+            content["schema"]["properties"]["error"]["properties"]["code"]["enum"],
+            json!(["ALREADY_SHIPPED", "LOCKED"])
+        );
+        assert_eq!(
+            content["examples"]["ALREADY_SHIPPED"]["summary"],
+            "The order was already shipped"
+        );
+        assert_eq!(
+            content["examples"]["ALREADY_SHIPPED"]["value"]["error"]["message"],
+            "Order {0} already shipped"
+        );
+    }
+
+    #[test]
+    fn describes_the_format() {
+        let responses = responses::<InnerError>();
+        let content = &responses["507"]["content"]["application/problem+json"];
+        let properties = &content["schema"]["properties"];
+        assert_eq!(properties["status"]["type"], "integer");
+        assert_eq!(properties["code"]["enum"], json!(["STORAGE"]));
+        assert_eq!(
+            content["examples"]["STORAGE"]["value"]["detail"],
+            "Storage failed"
+        );
+    }
+
+    #[test]
+    fn derives_to_schema() {
+        assert_eq!(OrderError::name(), "OrderError");
+
+        let schema = serde_json::to_value(OrderError::schema()).unwrap();
+        assert_eq!(
+            schema["properties"]["error"]["properties"]["code"]["enum"],
+            json!(["ORDER_NOT_FOUND", "ALREADY_SHIPPED", "LOCKED"])
+        );
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use std::convert::Infallible;
+
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Request, StatusCode},
+        response::{IntoResponse, Response},
+        routing::get,
+        Router,
+    };
+    use axum_error_handler::{CatchPanicLayer, RequestContextLayer};
+    use serde_json::{json, Value};
+    use tower::{service_fn, ServiceBuilder, ServiceExt};
+
+    async fn body_json(resp: Response) -> Value {
+        let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    async fn panics() -> &'static str {
+        panic!("secret connection string")
+    }
+
+    #[tokio::test]
+    async fn renders_handler_panics() {
+        let app = Router::new()
+            .route("/", get(panics))
+            .layer(CatchPanicLayer::new())
+            .layer(RequestContextLayer::new());
+
+        let req = Request::builder()
+            .uri("/")
+            .header("x-request-id", "req-42")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = body_json(resp).await;
+        assert_eq!(
+            body,
+            json!({
+                "result": null,
+                "error": {
+                    "code": "INTERNAL_PANIC",
+                    "message": "Internal Server Error",
+                    "trace_id": "req-42"
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_panics_while_calling() {
+        let service = ServiceBuilder::new()
+            .layer(CatchPanicLayer::new())
+            .service(service_fn(
+                |_: Request<Body>| -> std::future::Ready<Result<Response, Infallible>> {
+                    panic!("not ready")
+                },
+            ));
+
+        let resp = service.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body_json(resp).await["error"]["code"], "INTERNAL_PANIC");
+    }
+
+    #[tokio::test]
+    async fn passes_responses_through() {
+        let service = ServiceBuilder::new()
+            .layer(CatchPanicLayer::new())
+            .service(service_fn(|_: Request<Body>| async {
+                Ok::<_, Infallible>("ok".into_response())
+            }));
+
+        let resp = service.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use std::time::Duration;
+
+    use axum::{http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{
+        AxumErrorResponse, ErrorFormat, ErrorResponseContext, IntoErrorResponseContext,
+    };
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UserError {
+        #[error("User {id} not found")]
+        #[response(status_code = 404, code = "USER_NOT_FOUND", retry_after = 3)]
+        #[response(help = "https://docs.example.com/errors/USER_NOT_FOUND")]
+        NotFound {
+            #[response(extension)]
+            id: u64,
+        },
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem")]
+    pub enum PaymentError {
+        /// Out of credit
+        #[error("Your balance is too low")]
+        #[response(
+            status_code = 403,
+            code = "OUT_OF_CREDIT",
+            type = "https://example.com/credit"
+        )]
+        OutOfCredit,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "graphql")]
+    pub enum QueryError {
+        #[error("Not allowed")]
+        #[response(status_code = 403, code = "FORBIDDEN")]
+        Forbidden,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "text")]
+    pub enum HealthError {
+        #[error("Database unreachable")]
+        #[response(status_code = 503, code = "DB_DOWN")]
+        DatabaseDown,
+    }
+
+    #[tokio::test]
+    async fn parses_envelope_response() {
+        let resp = UserError::NotFound { id: 7 }.into_response();
+        let ctx = ErrorResponseContext::from_response(resp).await.unwrap();
+
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "USER_NOT_FOUND");
+        assert_eq!(ctx.message(), "User 7 not found");
+        assert_eq!(ctx.retry_after(), Some(Duration::from_secs(3)));
+        assert_eq!(
+            ctx.help_url(),
+            Some("https://docs.example.com/errors/USER_NOT_FOUND")
+        );
+        assert_eq!(ctx.extension_members()["id"], 7);
+    }
+
+    #[tokio::test]
+    async fn parses_problem_response() {
+        let resp = PaymentError::OutOfCredit.into_response();
+        let ctx = ErrorResponseContext::from_response(resp).await.unwrap();
+
+        assert_eq!(ctx, {
+            let expected = PaymentError::OutOfCredit.into_response_context();
+            ErrorResponseContext::builder()
+                .status_code(expected.status_code())
+                .code(expected.code())
+                .message(expected.message())
+                .title(expected.title().unwrap())
+                .type_uri(expected.type_uri().unwrap())
+                .format(ErrorFormat::Problem)
+                .build()
+        });
+    }
+
+    #[tokio::test]
+    async fn parses_graphql_and_text_responses() {
+        let ctx = ErrorResponseContext::from_response(QueryError::Forbidden.into_response())
+            .await
+            .unwrap();
+        assert_eq!(ctx.format(), ErrorFormat::GraphQl);
+        assert_eq!(ctx.code(), "FORBIDDEN");
+        assert_eq!(ctx.message(), "Not allowed");
+
+        let ctx = ErrorResponseContext::from_response(HealthError::DatabaseDown.into_response())
+            .await
+            .unwrap();
+        assert_eq!(ctx.format(), ErrorFormat::Text);
+        assert_eq!(ctx.code(), "DB_DOWN");
+        assert_eq!(ctx.message(), "Database unreachable");
+    }
+
+    #[tokio::test]
+    async fn rejects_other_responses() {
+        let resp = (StatusCode::OK, "[1, 2]").into_response();
+        assert!(ErrorResponseContext::from_response(resp).await.is_err());
+    }
+}
@@ -0,0 +1,348 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{AxumErrorResponse, ErrorFormat, ErrorResponseBuilder};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem")]
+    pub enum PaymentError {
+        /// You do not have enough credit.
+        #[error("Your current balance is 30, but that costs 50.")]
+        #[response(
+            status_code = 403,
+            code = "OUT_OF_CREDIT",
+            type = "https://example.com/probs/out-of-credit",
+            instance = "/account/12345/msgs/abc"
+        )]
+        OutOfCredit,
+        #[error("Card declined")]
+        #[response(status_code = 402, code = "CARD_DECLINED")]
+        Declined,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum MigratingError {
+        #[error("Order not found")]
+        #[response(status_code = 404, code = "ORDER_NOT_FOUND", format = "problem")]
+        NotFound,
+        #[error("Ledger locked")]
+        #[response(status_code = 423, code = "LEDGER_LOCKED")]
+        Locked,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem", content_type = "application/json")]
+    pub enum LegacyClientError {
+        #[error("Gone")]
+        #[response(status_code = 410)]
+        Gone,
+        #[error("Moved")]
+        #[response(status_code = 409, content_type = "application/vnd.myapp.error+json")]
+        Moved,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem")]
+    pub enum CreditError {
+        #[error("Your current balance is {balance}, but that costs {cost}.")]
+        #[response(status_code = 403, code = "OUT_OF_CREDIT")]
+        OutOfCredit {
+            #[response(extension)]
+            balance: u64,
+            cost: u64,
+            #[response(extension)]
+            accounts: Vec<String>,
+        },
+        #[error("Limit reached")]
+        #[response(status_code = 429)]
+        Limited(#[response(extension = "limit")] u32),
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem", type_base = "https://api.example.com/errors/")]
+    pub enum OrderError {
+        #[error("Order not found")]
+        #[response(status_code = 404, code = "ORDER_NOT_FOUND")]
+        NotFound,
+        #[error("Order rejected")]
+        #[response(status_code = 422, type = "https://example.com/rejected")]
+        Rejected,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "graphql")]
+    pub enum GatewayError {
+        #[error("Not allowed to read field `salary`")]
+        #[response(status_code = 403, code = "FORBIDDEN")]
+        Forbidden {
+            #[response(extension)]
+            field: String,
+        },
+    }
+
+    #[derive(Debug, serde::Serialize)]
+    pub struct Violation {
+        field: &'static str,
+        reason: &'static str,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem")]
+    pub enum SignupError {
+        #[error("Signup form is invalid")]
+        #[response(status_code = 422, code = "INVALID_SIGNUP")]
+        Invalid {
+            #[response(body)]
+            invalid_params: Violations,
+        },
+    }
+
+    #[derive(Debug, serde::Serialize)]
+    pub struct Violations {
+        #[serde(rename = "invalid-params")]
+        params: Vec<Violation>,
+    }
+
+    async fn body_json(resp: axum::response::Response) -> serde_json::Value {
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn renders_problem_details() {
+        let resp = PaymentError::OutOfCredit.into_response();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let body = body_json(resp).await;
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "type": "https://example.com/probs/out-of-credit",
+                "title": "You do not have enough credit.",
+                "status": 403,
+                "detail": "Your current balance is 30, but that costs 50.",
+                "instance": "/account/12345/msgs/abc",
+                "code": "OUT_OF_CREDIT",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_extension_members() {
+        let err = CreditError::OutOfCredit {
+            balance: 30,
+            cost: 50,
+            accounts: vec!["/account/12345".to_string()],
+        };
+        let body = body_json(err.into_response()).await;
+
+        assert_eq!(body["balance"], 30);
+        assert_eq!(body["accounts"], serde_json::json!(["/account/12345"]));
+        assert!(body.get("cost").is_none());
+
+        let body = body_json(CreditError::Limited(10).into_response()).await;
+        assert_eq!(body["limit"], 10);
+    }
+
+    #[tokio::test]
+    async fn prefixes_type_with_type_base() {
+        let body = body_json(OrderError::NotFound.into_response()).await;
+        assert_eq!(
+            body["type"],
+            "https://api.example.com/errors/ORDER_NOT_FOUND"
+        );
+
+        let body = body_json(OrderError::Rejected.into_response()).await;
+        assert_eq!(body["type"], "https://example.com/rejected");
+    }
+
+    #[tokio::test]
+    async fn fills_instance_from_request() {
+        use axum::{routing::get, Router};
+        use axum_error_handler::RequestContextLayer;
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/orders/:id", get(|| async { OrderError::NotFound }))
+            .route("/payments", get(|| async { PaymentError::OutOfCredit }))
+            .layer(RequestContextLayer::new());
+
+        let req = axum::http::Request::get("/orders/42?full=1")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let body = body_json(app.clone().oneshot(req).await.unwrap()).await;
+        assert_eq!(body["instance"], "/orders/42");
+
+        let req = axum::http::Request::get("/payments")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let body = body_json(app.oneshot(req).await.unwrap()).await;
+        assert_eq!(body["instance"], "/account/12345/msgs/abc");
+    }
+
+    #[tokio::test]
+    async fn uses_request_id_as_trace_id() {
+        use axum::{routing::get, Router};
+        use axum_error_handler::RequestContextLayer;
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/orders/:id", get(|| async { OrderError::NotFound }))
+            .layer(RequestContextLayer::new());
+
+        let req = axum::http::Request::get("/orders/42")
+            .header("x-request-id", "req-7f3a")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let body = body_json(app.oneshot(req).await.unwrap()).await;
+        assert_eq!(body["trace_id"], "req-7f3a");
+    }
+
+    #[tokio::test]
+    async fn prefers_traceparent_as_trace_id() {
+        use axum::{routing::get, Router};
+        use axum_error_handler::RequestContextLayer;
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/orders/:id", get(|| async { OrderError::NotFound }))
+            .layer(RequestContextLayer::new());
+
+        let req = axum::http::Request::get("/orders/42")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .header("x-request-id", "req-7f3a")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let body = body_json(app.clone().oneshot(req).await.unwrap()).await;
+        assert_eq!(body["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736");
+
+        let req = axum::http::Request::get("/orders/42")
+            .header(
+                "traceparent",
+                "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            )
+            .header("x-request-id", "req-7f3a")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let body = body_json(app.oneshot(req).await.unwrap()).await;
+        assert_eq!(body["trace_id"], "req-7f3a");
+    }
+
+    #[tokio::test]
+    async fn renders_graphql_errors() {
+        let err = GatewayError::Forbidden {
+            field: "salary".to_string(),
+        };
+        let body = body_json(err.into_response()).await;
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "errors": [{
+                    "message": "Not allowed to read field `salary`",
+                    "extensions": { "code": "FORBIDDEN", "field": "salary" },
+                }],
+                "data": null,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_builder_details() {
+        let ctx = || {
+            ErrorResponseBuilder::new()
+                .status_code(StatusCode::BAD_REQUEST)
+                .code("INVALID_INPUT".to_string())
+                .message("Invalid input".to_string())
+                .details(serde_json::json!({ "fields": ["email"] }))
+        };
+
+        let body = body_json(ctx().build().into_response()).await;
+        assert_eq!(body["error"]["details"]["fields"][0], "email");
+
+        let body = body_json(ctx().format(ErrorFormat::GraphQl).build().into_response()).await;
+        assert_eq!(
+            body["errors"][0]["extensions"]["details"]["fields"][0],
+            "email"
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_field_errors() {
+        let ctx = || {
+            ErrorResponseBuilder::new()
+                .status_code(StatusCode::UNPROCESSABLE_ENTITY)
+                .code("VALIDATION_FAILED")
+                .message("Validation failed")
+                .field_error("email", "INVALID_EMAIL", "must be a valid email address")
+        };
+        let expected = serde_json::json!([{
+            "field": "email",
+            "code": "INVALID_EMAIL",
+            "message": "must be a valid email address",
+        }]);
+
+        let body = body_json(ctx().build().into_response()).await;
+        assert_eq!(body["error"]["fields"], expected);
+
+        let body = body_json(ctx().format(ErrorFormat::Problem).build().into_response()).await;
+        assert_eq!(body["fields"], expected);
+    }
+
+    #[tokio::test]
+    async fn merges_body_field_into_problem() {
+        let err = SignupError::Invalid {
+            invalid_params: Violations {
+                params: vec![Violation {
+                    field: "age",
+                    reason: "must be a positive integer",
+                }],
+            },
+        };
+        let body = body_json(err.into_response()).await;
+
+        assert_eq!(
+            body["invalid-params"],
+            serde_json::json!([{ "field": "age", "reason": "must be a positive integer" }])
+        );
+    }
+
+    #[test]
+    fn uses_problem_content_type() {
+        let resp = PaymentError::Declined.into_response();
+        assert_eq!(resp.headers()["content-type"], "application/problem+json");
+
+        let resp = LegacyClientError::Gone.into_response();
+        assert_eq!(resp.headers()["content-type"], "application/json");
+
+        let resp = LegacyClientError::Moved.into_response();
+        assert_eq!(
+            resp.headers()["content-type"],
+            "application/vnd.myapp.error+json"
+        );
+    }
+
+    #[tokio::test]
+    async fn overrides_format_per_variant() {
+        let resp = MigratingError::NotFound.into_response();
+        assert_eq!(resp.headers()["content-type"], "application/problem+json");
+        assert_eq!(body_json(resp).await["detail"], "Order not found");
+
+        let body = body_json(MigratingError::Locked.into_response()).await;
+        assert_eq!(body["error"]["code"], "LEDGER_LOCKED");
+    }
+
+    #[tokio::test]
+    async fn problem_defaults_to_about_blank() {
+        let body = body_json(PaymentError::Declined.into_response()).await;
+
+        assert_eq!(body["type"], "about:blank");
+        assert_eq!(body["title"], "Payment Required");
+        assert!(body.get("instance").is_none());
+    }
+}
@@ -1,7 +1,17 @@
 #[cfg(test)]
+#[cfg(feature = "axum")]
 mod tests {
-    use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
-    use axum_error_handler::AxumErrorResponse;
+    use std::time::Duration;
+
+    use axum::{
+        body::to_bytes,
+        http::{header, HeaderValue, StatusCode},
+        response::IntoResponse,
+    };
+    use axum_error_handler::{
+        AxumErrorResponse, ErrorResponseBuilder, ErrorResponseContext, IntoErrorResponseContext,
+        Severity,
+    };
     use thiserror::Error;
 
     #[derive(Debug, Error, AxumErrorResponse)]
@@ -16,6 +26,165 @@ mod tests {
         AnotherNoStringError(#[from] InnerError),
     }
 
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum AuthError {
+        #[error("Missing token")]
+        #[status_code("401")]
+        #[code("UNAUTHORIZED")]
+        #[response(header("WWW-Authenticate", "Bearer"))]
+        #[response(header("Cache-Control", "no-store"))]
+        MissingToken,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum ThrottleError {
+        #[error("Too many requests")]
+        #[status_code("429")]
+        #[code("TOO_MANY_REQUESTS")]
+        #[response(retry_after = 30)]
+        TooManyRequests,
+        #[error("Service unavailable, retry in {secs}s")]
+        #[status_code("503")]
+        #[code("SERVICE_UNAVAILABLE")]
+        #[response(retry_after_field = "secs")]
+        Unavailable { secs: u64 },
+        #[error("Backing off")]
+        #[response(status_code = 503, retry_after_field = 0)]
+        BackOff(std::time::Duration),
+    }
+
+    mod codes {
+        pub const USER_NOT_FOUND: &str = "USER_NOT_FOUND";
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UserError {
+        #[error("User {0} not found")]
+        #[status_code(StatusCode::NOT_FOUND)]
+        #[code(codes::USER_NOT_FOUND)]
+        NotFound(u64),
+        #[error("User is locked")]
+        #[status_code(423)]
+        Locked,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UpstreamError {
+        #[error("Upstream responded with {status}")]
+        #[code("UPSTREAM_ERROR")]
+        #[response(status_from = "status")]
+        Named { status: u16 },
+        #[error("Upstream responded with {0}")]
+        #[code("UPSTREAM_ERROR")]
+        #[response(status_from = 0)]
+        Typed(StatusCode),
+        #[error("Upstream failed: {message}")]
+        #[response(status_from = "status", code_from = "kind")]
+        Coded {
+            status: u16,
+            kind: String,
+            message: String,
+        },
+    }
+
+    /// Errors raised by the billing service.
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum BillingError {
+        /// Insufficient funds
+        ///
+        /// The account balance does not cover the requested charge.
+        #[error("Balance {0} is too low")]
+        #[status_code("403")]
+        OutOfCredit(u64),
+        #[error("Billing is down")]
+        Unavailable,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum AccountError {
+        /// Account missing
+        #[error("Account {0} not found")]
+        #[response(
+            status_code = 404,
+            code = "ACCOUNT_NOT_FOUND",
+            type = "https://example.com/probs/account-not-found",
+            title = "Account not found",
+            instance = "/accounts"
+        )]
+        NotFound(u64),
+        #[error("Account is frozen")]
+        #[response(status_code = StatusCode::FORBIDDEN, code = codes::USER_NOT_FOUND)]
+        Frozen,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "text")]
+    pub enum HealthError {
+        #[error("database unreachable")]
+        #[response(status_code = 503, code = "DB_DOWN")]
+        DatabaseDown,
+    }
+
+    #[derive(Debug, serde::Serialize)]
+    pub struct Conflict {
+        id: u64,
+        owner: String,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum ReservationError {
+        #[error("Seat is already reserved")]
+        #[response(status_code = 409, code = "SEAT_TAKEN")]
+        Taken(#[response(body)] Conflict),
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(envelope(result = "data", error = "err", message = "msg"))]
+    pub enum LegacyError {
+        #[error("Session expired")]
+        #[response(status_code = 401, code = "SESSION_EXPIRED")]
+        SessionExpired,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(envelope(omit_result))]
+    pub enum ErrorOnlyError {
+        #[error("Nope")]
+        #[response(status_code = 400, code = "NOPE")]
+        Nope,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(envelope(flat))]
+    pub enum FlatError {
+        #[error("Nope")]
+        #[response(status_code = 400, code = "NOPE")]
+        Nope,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum LookupError {
+        #[error("Cache miss")]
+        #[response(status_code = 404, severity = "info")]
+        CacheMiss,
+        #[error("Lookup failed")]
+        #[response(status_code = 502)]
+        Failed,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum ProfileError {
+        #[error("User {user_id} not found")]
+        #[response(status_code = 404, code = "USER_NOT_FOUND")]
+        #[response(message_key = "errors.user.not_found")]
+        #[response(help = "https://docs.example.com/errors/USER_NOT_FOUND")]
+        NotFound {
+            #[response(message_arg)]
+            user_id: u64,
+        },
+    }
+
+    #[allow(dead_code)]
     #[derive(Debug, Error)]
     pub enum InnerError {
         #[error("Bad request: {0}")]
@@ -47,6 +216,305 @@ mod tests {
         assert_eq!(status_code, 400);
     }
 
+    #[test]
+    fn has_static_headers() {
+        let resp = AuthError::MissingToken.into_response();
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(resp.headers()["www-authenticate"], "Bearer");
+        assert_eq!(resp.headers()["cache-control"], "no-store");
+    }
+
+    #[test]
+    fn applies_context_headers() {
+        let ctx = AuthError::MissingToken.into_response_context();
+        assert_eq!(ctx.headers()["www-authenticate"], "Bearer");
+
+        let resp = ErrorResponseBuilder::new()
+            .status_code(StatusCode::NOT_ACCEPTABLE)
+            .header(header::VARY, HeaderValue::from_static("Accept"))
+            .header(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/vnd.myapp+json"),
+            )
+            .build()
+            .into_response();
+
+        assert_eq!(resp.headers()["vary"], "Accept");
+        assert_eq!(resp.headers()["content-type"], "application/vnd.myapp+json");
+        assert_eq!(
+            resp.headers().get_all(header::CONTENT_TYPE).iter().count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn has_retry_after() {
+        let resp = ThrottleError::TooManyRequests.into_response();
+        assert_eq!(resp.headers()["retry-after"], "30");
+
+        let resp = ThrottleError::Unavailable { secs: 12 }.into_response();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()["retry-after"], "12");
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"]["retry_after"], 12);
+
+        let resp = ThrottleError::BackOff(Duration::from_millis(1500)).into_response();
+        assert_eq!(resp.headers()["retry-after"], "2");
+    }
+
+    #[test]
+    fn sets_retry_after_from_builder() {
+        let ctx = ErrorResponseBuilder::new()
+            .status_code(StatusCode::TOO_MANY_REQUESTS)
+            .retry_after(Duration::from_secs(90))
+            .build();
+        assert_eq!(ctx.retry_after(), Some(Duration::from_secs(90)));
+
+        let resp = ctx.into_response();
+        assert_eq!(resp.headers()["retry-after"], "90");
+    }
+
+    #[tokio::test]
+    async fn accepts_const_expressions() {
+        let resp = UserError::NotFound(7).into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"]["code"], codes::USER_NOT_FOUND);
+
+        let resp = UserError::Locked.into_response();
+        assert_eq!(resp.status(), StatusCode::LOCKED);
+    }
+
+    #[test]
+    fn uses_status_from_field() {
+        let resp = UpstreamError::Named { status: 502 }.into_response();
+        assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+
+        let resp = UpstreamError::Named { status: 42 }.into_response();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let resp = UpstreamError::Typed(StatusCode::NOT_FOUND).into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn uses_code_from_field() {
+        let err = UpstreamError::Coded {
+            status: 409,
+            kind: "DUPLICATE_ORDER".to_string(),
+            message: "order exists".to_string(),
+        };
+
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"]["code"], "DUPLICATE_ORDER");
+        assert_eq!(body["error"]["message"], "Upstream failed: order exists");
+    }
+
+    #[test]
+    fn uses_doc_comments_as_title() {
+        let ctx = BillingError::OutOfCredit(5).into_response_context();
+        assert_eq!(ctx.title(), Some("Insufficient funds"));
+        assert_eq!(
+            ctx.type_description(),
+            Some("Errors raised by the billing service.")
+        );
+        assert_eq!(ctx.message(), "Balance 5 is too low");
+
+        let ctx = BillingError::Unavailable.into_response_context();
+        assert_eq!(ctx.title(), None);
+    }
+
+    #[test]
+    fn parses_unified_response_attribute() {
+        let ctx = AccountError::NotFound(3).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "ACCOUNT_NOT_FOUND");
+        assert_eq!(
+            ctx.type_uri(),
+            Some("https://example.com/probs/account-not-found")
+        );
+        assert_eq!(ctx.title(), Some("Account not found"));
+        assert_eq!(ctx.instance(), Some("/accounts"));
+
+        let ctx = AccountError::Frozen.into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::FORBIDDEN);
+        assert_eq!(ctx.code(), codes::USER_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn renders_plain_text() {
+        let resp = HealthError::DatabaseDown.into_response();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()["content-type"], "text/plain; charset=utf-8");
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        assert_eq!(&bytes[..], b"DB_DOWN: database unreachable");
+
+        let resp = UserError::Locked
+            .into_response_context()
+            .into_text_response();
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        assert_eq!(&bytes[..], b"Locked: User is locked");
+    }
+
+    #[tokio::test]
+    async fn serializes_body_field_as_details() {
+        let err = ReservationError::Taken(Conflict {
+            id: 12,
+            owner: "ana".to_string(),
+        });
+
+        let bytes = to_bytes(err.into_response().into_body(), 10485760)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"]["message"], "Seat is already reserved");
+        assert_eq!(
+            body["error"]["details"],
+            serde_json::json!({ "id": 12, "owner": "ana" })
+        );
+    }
+
+    #[tokio::test]
+    async fn renames_envelope_keys() {
+        let resp = LegacyError::SessionExpired.into_response();
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "data": null,
+                "err": { "code": "SESSION_EXPIRED", "msg": "Session expired" },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn omits_result_member() {
+        let bytes = to_bytes(ErrorOnlyError::Nope.into_response().into_body(), 10485760)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "error": { "code": "NOPE", "message": "Nope" } })
+        );
+
+        let bytes = to_bytes(FlatError::Nope.into_response().into_body(), 10485760)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "code": "NOPE", "message": "Nope" })
+        );
+    }
+
+    #[test]
+    fn carries_typed_extensions() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Tenant(&'static str);
+
+        let mut ctx = AuthError::MissingToken.into_response_context();
+        ctx.extensions_mut().insert(Tenant("acme"));
+        assert_eq!(ctx.extensions().get::<Tenant>(), Some(&Tenant("acme")));
+
+        let resp = ctx.into_response();
+        assert_eq!(resp.extensions().get::<Tenant>(), Some(&Tenant("acme")));
+    }
+
+    #[test]
+    fn declares_severity() {
+        let ctx = LookupError::CacheMiss.into_response_context();
+        assert_eq!(ctx.severity(), Severity::Info);
+
+        let ctx = LookupError::Failed.into_response_context();
+        assert_eq!(ctx.severity(), Severity::Error);
+    }
+
+    #[test]
+    fn compares_contexts() {
+        let expected = ErrorResponseBuilder::new()
+            .status_code(StatusCode::NOT_FOUND)
+            .code("CacheMiss")
+            .message("Cache miss")
+            .severity(Severity::Info)
+            .build();
+
+        let ctx = LookupError::CacheMiss.into_response_context();
+        assert_eq!(ctx, expected);
+        assert!(format!("{ctx:?}").contains("CacheMiss"));
+
+        let ctx = LookupError::Failed.into_response_context();
+        assert_ne!(ctx, expected);
+    }
+
+    #[test]
+    fn merges_and_overrides_contexts() {
+        let child = AuthError::MissingToken.into_response_context();
+
+        let ctx = ErrorResponseBuilder::new()
+            .status_code(StatusCode::BAD_GATEWAY)
+            .code("UPSTREAM_AUTH")
+            .header(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"))
+            .build()
+            .merge(child);
+        assert_eq!(ctx.status_code(), StatusCode::BAD_GATEWAY);
+        assert_eq!(ctx.code(), "UPSTREAM_AUTH");
+        assert_eq!(ctx.message(), "Missing token");
+        assert_eq!(ctx.headers()["cache-control"], "no-cache");
+        assert_eq!(ctx.headers()["www-authenticate"], "Bearer");
+
+        let ctx = AuthError::MissingToken
+            .into_response_context()
+            .override_with(|builder| {
+                builder
+                    .status_code(StatusCode::FORBIDDEN)
+                    .code_prefix("GATEWAY_")
+            });
+        assert_eq!(ctx.status_code(), StatusCode::FORBIDDEN);
+        assert_eq!(ctx.code(), "GATEWAY_UNAUTHORIZED");
+        assert_eq!(ctx.message(), "Missing token");
+    }
+
+    #[test]
+    fn builds_presets_from_status() {
+        let ctx = ErrorResponseContext::not_found();
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "NOT_FOUND");
+        assert_eq!(ctx.message(), "Not Found");
+
+        let ctx = ErrorResponseContext::from_status(StatusCode::IM_A_TEAPOT);
+        assert_eq!(ctx.code(), "I_M_A_TEAPOT");
+
+        let ctx = ErrorResponseContext::from_status(StatusCode::from_u16(599).unwrap());
+        assert_eq!(ctx.code(), "HTTP_599");
+        assert_eq!(ctx.message(), "599");
+    }
+
+    #[tokio::test]
+    async fn renders_message_key() {
+        let resp = ProfileError::NotFound { user_id: 7 }.into_response();
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(body["error"]["message_key"], "errors.user.not_found");
+        assert_eq!(
+            body["error"]["message_args"],
+            serde_json::json!({ "user_id": 7 })
+        );
+    }
+
     #[test]
     fn parse_http_code() {
         let status_code = StatusCode::from_u16(400).unwrap();
@@ -0,0 +1,49 @@
+#[cfg(test)]
+#[cfg(feature = "redis")]
+mod tests {
+    use std::io;
+
+    use axum::{http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{AxumErrorResponse, IntoErrorResponseContext};
+    use redis::{ErrorKind, RedisError};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum CacheError {
+        #[error(transparent)]
+        #[response(nested)]
+        Redis(#[from] RedisError),
+    }
+
+    #[test]
+    fn maps_unreachable_servers_to_503() {
+        let timeout = io::Error::new(io::ErrorKind::TimedOut, "timed out");
+        let ctx = RedisError::from(timeout).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(ctx.code(), "SERVICE_UNAVAILABLE");
+
+        let refused = io::Error::new(io::ErrorKind::ConnectionRefused, "refused");
+        let ctx = RedisError::from(refused).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let loading = RedisError::from((ErrorKind::BusyLoadingError, "loading"));
+        let ctx = loading.into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn maps_other_errors_to_500() {
+        let err = RedisError::from((ErrorKind::TypeError, "expected an integer"));
+        let ctx = err.into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ctx.message(), "Internal Server Error");
+        assert!(ctx.sources()[0].contains("expected an integer"));
+    }
+
+    #[test]
+    fn renders_nested_redis_errors() {
+        let err = io::Error::new(io::ErrorKind::ConnectionReset, "reset");
+        let resp = CacheError::from(RedisError::from(err)).into_response();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+#[cfg(feature = "rejections")]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        extract::{
+            rejection::{JsonRejection, PathRejection, QueryRejection},
+            FromRequest, Path, Query,
+        },
+        http::{header, Request, StatusCode},
+        routing::post,
+        Json, Router,
+    };
+    use axum_error_handler::{AxumErrorResponse, IntoErrorResponseContext};
+    use serde::Deserialize;
+    use thiserror::Error;
+    use tower::ServiceExt;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    pub struct NewItem {
+        name: String,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum ItemError {
+        #[error(transparent)]
+        #[response(nested)]
+        Json(#[from] JsonRejection),
+    }
+
+    async fn json_rejection(body: &'static str, content_type: &str) -> JsonRejection {
+        let req = Request::post("/")
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap();
+
+        Json::<NewItem>::from_request(req, &()).await.unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn codes_json_rejections() {
+        let ctx = json_rejection("{", "application/json")
+            .await
+            .into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(ctx.code(), "INVALID_JSON_BODY");
+
+        let ctx = json_rejection("{}", "application/json")
+            .await
+            .into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(ctx.code(), "INVALID_JSON_DATA");
+        assert!(ctx.message().contains("missing field `name`"));
+
+        let ctx = json_rejection("{}", "text/plain")
+            .await
+            .into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(ctx.code(), "MISSING_JSON_CONTENT_TYPE");
+    }
+
+    #[tokio::test]
+    async fn codes_path_and_query_rejections() {
+        let app = Router::new().route(
+            "/items/:id",
+            post(
+                |id: Result<Path<u64>, PathRejection>,
+                 query: Result<Query<NewItemQuery>, QueryRejection>| async move {
+                    let ctx = match (id, query) {
+                        (Err(rejection), _) => rejection.into_response_context(),
+                        (_, Err(rejection)) => rejection.into_response_context(),
+                        _ => return String::new(),
+                    };
+                    format!("{} {}", ctx.status_code().as_u16(), ctx.code())
+                },
+            ),
+        );
+
+        let req = Request::post("/items/abc?limit=1")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "400 INVALID_PATH_PARAMS");
+
+        let req = Request::post("/items/1?limit=x")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "400 INVALID_QUERY_STRING");
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    pub struct NewItemQuery {
+        limit: u32,
+    }
+
+    #[tokio::test]
+    async fn renders_nested_rejections() {
+        let app = Router::new().route(
+            "/items",
+            post(|item: Result<Json<NewItem>, JsonRejection>| async move {
+                item.map(|_| StatusCode::CREATED).map_err(ItemError::from)
+            }),
+        );
+
+        let req = Request::post("/items")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("not json"))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "INVALID_JSON_BODY");
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use axum::{
+        body::to_bytes,
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    };
+    use axum_error_handler::{AxumErrorResponse, ErrorResponseContext};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum FeedError {
+        #[error("Feed not found")]
+        #[response(status_code = 404, code = "FEED_NOT_FOUND")]
+        NotFound,
+        #[error("Feed unavailable")]
+        #[response(status_code = 503, code = "FEED_DOWN")]
+        Unavailable,
+    }
+
+    fn render(ctx: ErrorResponseContext) -> Response {
+        if ctx.status_code().is_server_error() {
+            return ctx.into_default_response();
+        }
+
+        (
+            ctx.status_code(),
+            format!("{} ({})", ctx.message(), ctx.code()),
+        )
+            .into_response()
+    }
+
+    async fn body_string(resp: Response) -> String {
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn uses_global_default_renderer() {
+        axum_error_handler::set_default_renderer(render).unwrap();
+
+        let resp = FeedError::NotFound.into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body_string(resp).await, "Feed not found (FEED_NOT_FOUND)");
+
+        let resp = FeedError::Unavailable.into_response();
+        assert_eq!(
+            body_string(resp).await,
+            r#"{"error":{"code":"FEED_DOWN","message":"Feed unavailable"},"result":null}"#
+        );
+    }
+}
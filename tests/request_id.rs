@@ -0,0 +1,102 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{HeaderName, Request},
+        routing::get,
+        Extension, Router,
+    };
+    use axum_error_handler::{AxumErrorResponse, RequestId, RequestIdLayer};
+    use thiserror::Error;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum OrderError {
+        #[error("Order not found")]
+        #[response(status_code = 404, code = "ORDER_NOT_FOUND")]
+        NotFound,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem")]
+    pub enum PaymentError {
+        #[error("Payment declined")]
+        #[response(status_code = 402, code = "DECLINED")]
+        Declined,
+    }
+
+    fn app(layer: RequestIdLayer) -> Router {
+        Router::new()
+            .route("/orders", get(|| async { OrderError::NotFound }))
+            .route("/payments", get(|| async { PaymentError::Declined }))
+            .route(
+                "/id",
+                get(|Extension(id): Extension<RequestId>| async move {
+                    id.as_str().unwrap().to_string()
+                }),
+            )
+            .layer(layer)
+    }
+
+    async fn body_json(resp: axum::response::Response) -> serde_json::Value {
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn renders_the_incoming_request_id() {
+        let req = Request::get("/orders")
+            .header("x-request-id", "req-42")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app(RequestIdLayer::new()).oneshot(req).await.unwrap();
+        assert_eq!(resp.headers()["x-request-id"], "req-42");
+
+        let body = body_json(resp).await;
+        assert_eq!(body["error"]["request_id"], "req-42");
+        assert_eq!(body["error"]["code"], "ORDER_NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn generates_missing_ids() {
+        let req = Request::get("/orders").body(Body::empty()).unwrap();
+        let resp = app(RequestIdLayer::new()).oneshot(req).await.unwrap();
+        let id = resp.headers()["x-request-id"].to_str().unwrap().to_string();
+        assert_eq!(id.len(), 32);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+
+        let body = body_json(resp).await;
+        assert_eq!(body["error"]["request_id"], id.as_str());
+
+        let req = Request::get("/orders").body(Body::empty()).unwrap();
+        let resp = app(RequestIdLayer::new()).oneshot(req).await.unwrap();
+        assert_ne!(resp.headers()["x-request-id"], id.as_str());
+    }
+
+    #[tokio::test]
+    async fn stores_the_id_in_extensions() {
+        let layer = RequestIdLayer::new().header(HeaderName::from_static("x-correlation-id"));
+        let req = Request::get("/id")
+            .header("x-correlation-id", "corr-7")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app(layer).oneshot(req).await.unwrap();
+        assert_eq!(resp.headers()["x-correlation-id"], "corr-7");
+
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        assert_eq!(&bytes[..], b"corr-7");
+    }
+
+    #[tokio::test]
+    async fn adds_the_id_to_other_formats() {
+        let req = Request::get("/payments")
+            .header("x-request-id", "req-43")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app(RequestIdLayer::new()).oneshot(req).await.unwrap();
+        let body = body_json(resp).await;
+        assert_eq!(body["request_id"], "req-43");
+        assert_eq!(body["code"], "DECLINED");
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use axum_error_handler::AxumErrorResponse;
+    use serde_json::json;
+    use thiserror::Error;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UserError {
+        #[error("User not found")]
+        #[response(status_code = 404, code = "NOT_FOUND")]
+        NotFound,
+        #[error("Database failed")]
+        #[response(status_code = 500, code = "DB_FAILED", internal)]
+        Database,
+        #[error("User is banned")]
+        #[response(status_code = 403, code = "BANNED")]
+        Banned,
+    }
+
+    #[test]
+    fn lists_derived_and_builtin_codes() {
+        let schema = axum_error_handler::envelope_json_schema();
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        assert_eq!(schema["required"], json!(["result", "error"]));
+        assert_eq!(schema["properties"]["result"], json!({ "type": "null" }));
+
+        let error = &schema["properties"]["error"];
+        assert_eq!(error["required"], json!(["code", "message"]));
+        assert_eq!(error["properties"]["code"]["type"], "string");
+
+        let codes = error["properties"]["code"]["enum"].as_array().unwrap();
+        for code in [
+            "BANNED",
+            "INTERNAL_ERROR",
+            "INTERNAL_SERVER_ERROR",
+            "NOT_FOUND",
+        ] {
+            assert!(codes.contains(&json!(code)), "{code} is missing");
+        }
+        #[cfg(feature = "axum")]
+        assert!(codes.contains(&json!("ROUTE_NOT_FOUND")));
+        assert!(!codes.contains(&json!("DB_FAILED")));
+        assert!(codes
+            .windows(2)
+            .all(|pair| pair[0].as_str() < pair[1].as_str()));
+
+        let schema = axum_error_handler::problem_json_schema();
+        assert_eq!(
+            schema["required"],
+            json!(["type", "title", "status", "detail", "code"])
+        );
+        assert_eq!(&schema["properties"]["code"], &error["properties"]["code"]);
+        assert_eq!(
+            schema["properties"]["fields"]["items"]["required"],
+            json!(["field", "code", "message"])
+        );
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+#[cfg(feature = "sea-orm")]
+mod tests {
+    use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{AxumErrorResponse, IntoErrorResponseContext};
+    use sea_orm::{ConnAcquireErr, DbErr, RuntimeErr};
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum PostError {
+        #[error(transparent)]
+        #[response(nested)]
+        Db(#[from] DbErr),
+    }
+
+    #[test]
+    fn maps_db_errors() {
+        let ctx = DbErr::RecordNotFound("post 7".to_string()).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "NOT_FOUND");
+
+        let ctx = DbErr::ConnectionAcquire(ConnAcquireErr::Timeout).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let ctx = DbErr::Conn(RuntimeErr::Internal("refused".to_string())).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn redacts_query_errors() {
+        let err = DbErr::Query(RuntimeErr::Internal(
+            "syntax error at or near \"FORM\"".to_string(),
+        ));
+        let ctx = err.into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ctx.message(), "Internal Server Error");
+        assert!(ctx.sources()[0].contains("FORM"));
+    }
+
+    #[tokio::test]
+    async fn renders_nested_db_errors() {
+        let err = PostError::from(DbErr::RecordNotFound("post 7".to_string()));
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(
+            body,
+            r#"{"error":{"code":"NOT_FOUND","message":"Not Found"},"result":null}"#
+        );
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+#[cfg(feature = "sentry")]
+mod tests {
+    use axum::response::IntoResponse;
+    use axum_error_handler::AxumErrorResponse;
+    use sentry_core::protocol::Level;
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    #[error("socket closed")]
+    pub struct SocketError;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum PaymentError {
+        #[error("Card declined")]
+        #[response(status_code = 402, code = "CARD_DECLINED")]
+        Declined,
+        #[error("Payment gateway unavailable")]
+        #[response(status_code = 503, code = "GATEWAY_DOWN")]
+        GatewayDown(#[source] SocketError),
+    }
+
+    #[test]
+    fn captures_server_errors() {
+        let events = sentry_core::test::with_captured_events(|| {
+            let _ = PaymentError::Declined.into_response();
+            let _ = PaymentError::GatewayDown(SocketError).into_response();
+        });
+
+        assert_eq!(events.len(), 1);
+
+        let event = &events[0];
+        assert_eq!(event.level, Level::Error);
+        assert_eq!(
+            event.message.as_deref(),
+            Some("Payment gateway unavailable")
+        );
+        assert_eq!(event.tags["error.code"], "GATEWAY_DOWN");
+        assert_eq!(event.tags["http.status"], "503");
+
+        let exceptions: Vec<_> = event
+            .exception
+            .iter()
+            .map(|exception| (exception.ty.as_str(), exception.value.as_deref()))
+            .collect();
+        assert_eq!(
+            exceptions,
+            [
+                ("source", Some("socket closed")),
+                ("GATEWAY_DOWN", Some("Payment gateway unavailable")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use axum::{body::to_bytes, response::IntoResponse};
+    use axum_error_handler::{AxumErrorResponse, ErrorResponseContext, IntoErrorResponseContext};
+    use serde::Serialize;
+    use thiserror::Error;
+
+    #[derive(Debug, Serialize)]
+    pub struct Conflict {
+        holder: &'static str,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(format = "problem")]
+    pub enum BookingError {
+        /// Seat unavailable
+        #[error("Seat {seat} is already reserved")]
+        #[response(status_code = 409, code = "SEAT_TAKEN", retry_after = 5)]
+        #[response(header("Cache-Control", "no-store"), severity = "info")]
+        Taken {
+            #[response(extension)]
+            seat: u32,
+            #[response(body)]
+            conflict: Conflict,
+        },
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(envelope(result = "data", error = "err"))]
+    pub enum LegacyError {
+        #[error("Legacy failure")]
+        #[response(status_code = 502, code = "LEGACY_FAILED")]
+        Failed,
+    }
+
+    async fn parts(resp: axum::response::Response) -> (u16, Vec<(String, String)>, Vec<u8>) {
+        let status = resp.status().as_u16();
+        let headers = resp
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_str().unwrap().to_string()))
+            .collect();
+        let body = to_bytes(resp.into_body(), 10485760).await.unwrap().to_vec();
+
+        (status, headers, body)
+    }
+
+    fn context() -> ErrorResponseContext {
+        BookingError::Taken {
+            seat: 12,
+            conflict: Conflict { holder: "alice" },
+        }
+        .into_response_context()
+    }
+
+    #[tokio::test]
+    async fn round_trips_through_json() {
+        let ctx = context();
+        let json = serde_json::to_value(&ctx).unwrap();
+
+        assert_eq!(json["status"], 409);
+        assert_eq!(json["code"], "SEAT_TAKEN");
+        assert_eq!(json["format"], "problem");
+        assert_eq!(json["severity"], "info");
+        assert_eq!(
+            json["headers"],
+            serde_json::json!([["cache-control", "no-store"]])
+        );
+
+        let restored: ErrorResponseContext = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, ctx);
+        assert_eq!(restored.timestamp(), ctx.timestamp());
+        assert_eq!(
+            parts(restored.into_response()).await,
+            parts(context().into_response()).await
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_status() {
+        let json = serde_json::json!({
+            "status": 1000,
+            "code": "X",
+            "message": "x",
+            "timestamp": "2024-01-01T00:00:00Z",
+        });
+
+        assert!(serde_json::from_value::<ErrorResponseContext>(json).is_err());
+    }
+
+    #[tokio::test]
+    async fn does_not_round_trip_envelope() {
+        let ctx = LegacyError::Failed.into_response_context();
+        assert!(ctx.envelope().is_some());
+
+        let json = serde_json::to_value(&ctx).unwrap();
+        let restored: ErrorResponseContext = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.envelope(), None);
+
+        let body = to_bytes(restored.into_response().into_body(), 10485760)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "LEGACY_FAILED");
+        assert_eq!(body["result"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+#[cfg(feature = "sqlx")]
+mod tests {
+    use std::{borrow::Cow, error::Error, fmt};
+
+    use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{AxumErrorResponse, IntoErrorResponseContext, SqlxError};
+    use sqlx::error::{DatabaseError, ErrorKind};
+    use thiserror::Error;
+
+    #[derive(Debug)]
+    pub struct FakeDbError(ErrorKind);
+
+    impl fmt::Display for FakeDbError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("duplicate key value violates unique constraint \"users_email_key\"")
+        }
+    }
+
+    impl Error for FakeDbError {}
+
+    impl DatabaseError for FakeDbError {
+        fn message(&self) -> &str {
+            "duplicate key value violates unique constraint \"users_email_key\""
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> ErrorKind {
+            match self.0 {
+                ErrorKind::UniqueViolation => ErrorKind::UniqueViolation,
+                ErrorKind::ForeignKeyViolation => ErrorKind::ForeignKeyViolation,
+                _ => ErrorKind::Other,
+            }
+        }
+    }
+
+    fn database_error(kind: ErrorKind) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(FakeDbError(kind)))
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UserError {
+        #[error(transparent)]
+        #[response(nested)]
+        Db(#[from] sqlx::Error),
+    }
+
+    #[test]
+    fn maps_sqlx_errors() {
+        let ctx = sqlx::Error::RowNotFound.into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ctx.code(), "NOT_FOUND");
+
+        let ctx = database_error(ErrorKind::UniqueViolation).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::CONFLICT);
+        assert_eq!(ctx.code(), "CONFLICT");
+
+        let ctx = database_error(ErrorKind::ForeignKeyViolation).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let ctx = sqlx::Error::PoolTimedOut.into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn redacts_other_errors() {
+        let ctx = database_error(ErrorKind::Other).into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ctx.message(), "Internal Server Error");
+        assert!(ctx.sources()[0].contains("users_email_key"));
+    }
+
+    #[tokio::test]
+    async fn renders_the_wrapper_and_nested_errors() {
+        let resp = SqlxError::from(sqlx::Error::RowNotFound).into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = UserError::from(database_error(ErrorKind::UniqueViolation)).into_response();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(
+            body,
+            r#"{"error":{"code":"CONFLICT","message":"Conflict"},"result":null}"#
+        );
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use std::convert::Infallible;
+
+    use axum::{
+        body::to_bytes,
+        response::{sse::Event, IntoResponse, Sse},
+    };
+    use axum_error_handler::{AxumErrorResponse, IntoErrorResponseContext};
+    use futures_util::stream;
+    use thiserror::Error;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum FeedError {
+        #[error("Feed {0} was deleted")]
+        #[response(status_code = 410, code = "FEED_DELETED")]
+        Deleted(u32),
+    }
+
+    #[tokio::test]
+    async fn renders_error_events() {
+        let events = vec![
+            Ok::<_, Infallible>(Event::default().data("first")),
+            Ok(FeedError::Deleted(7)
+                .into_response_context()
+                .into_sse_event()),
+        ];
+        let resp = Sse::new(stream::iter(events)).into_response();
+
+        let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(
+            String::from_utf8(bytes.to_vec()).unwrap(),
+            "data: first\n\n\
+             event: error\n\
+             data: {\"error\":{\"code\":\"FEED_DELETED\",\"message\":\"Feed 7 was deleted\"},\"result\":null}\n\n"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+#[cfg(feature = "axum")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+    use axum_error_handler::{ErrorFormat, ErrorResponseContext};
+
+    async fn body_json(resp: axum::response::Response) -> serde_json::Value {
+        let bytes = to_bytes(resp.into_body(), 10485760).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn renders_timestamp_when_enabled() {
+        axum_error_handler::set_include_timestamp(true).unwrap();
+
+        let ctx = || {
+            ErrorResponseContext::builder()
+                .status_code(StatusCode::BAD_GATEWAY)
+                .code("UPSTREAM".to_string())
+                .message("Upstream failed".to_string())
+                .timestamp(UNIX_EPOCH + Duration::from_millis(1_700_000_000_123))
+        };
+
+        let body = body_json(ctx().build().into_response()).await;
+        assert_eq!(body["error"]["timestamp"], "2023-11-14T22:13:20.123Z");
+
+        let body = body_json(ctx().format(ErrorFormat::Problem).build().into_response()).await;
+        assert_eq!(body["timestamp"], "2023-11-14T22:13:20.123Z");
+    }
+}
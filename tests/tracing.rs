@@ -0,0 +1,129 @@
+#[cfg(test)]
+#[cfg(feature = "tracing")]
+mod tests {
+    use std::{
+        fmt,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::response::IntoResponse;
+    use axum_error_handler::AxumErrorResponse;
+    use thiserror::Error;
+    use tracing::{field::Field, Level};
+    use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum ExportError {
+        #[error("Export failed")]
+        #[response(status_code = 500, code = "EXPORT_FAILED")]
+        Failed,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum QuotaError {
+        #[error("Quota almost exhausted")]
+        #[response(status_code = 429, code = "QUOTA_LOW", log = "warn")]
+        Low,
+        #[error("Quota store down")]
+        #[response(status_code = 503, code = "QUOTA_STORE_DOWN", log = "error")]
+        StoreDown,
+        #[error("Quota probe")]
+        #[response(status_code = 404, code = "QUOTA_PROBE", log = "off")]
+        Probe,
+    }
+
+    /// Level and fields, formatted as `name=value`, of an event.
+    type Event = (Level, Vec<String>);
+
+    #[derive(Clone, Default)]
+    struct Events(Arc<Mutex<Vec<Event>>>);
+
+    struct FieldVisitor<'a>(&'a mut Vec<String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    impl<S: tracing::Subscriber> Layer<S> for Events {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            let mut fields = Vec::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.0
+                .lock()
+                .unwrap()
+                .push((*event.metadata().level(), fields));
+        }
+    }
+
+    #[test]
+    fn logs_variants_at_declared_level() {
+        let events = Events::default();
+        let subscriber = tracing_subscriber::registry().with(events.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _ = QuotaError::Low.into_response();
+            let _ = QuotaError::StoreDown.into_response();
+            let _ = QuotaError::Probe.into_response();
+            let _ = ExportError::Failed.into_response();
+        });
+
+        let events = events.0.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].0, Level::WARN);
+        assert_eq!(
+            events[0].1,
+            [
+                "message=error response",
+                "error.code=QUOTA_LOW",
+                "http.status=429",
+                "error.message=Quota almost exhausted",
+                "error.sources=[]",
+            ]
+        );
+        assert_eq!(events[1].0, Level::ERROR);
+        assert!(events[1]
+            .1
+            .contains(&"error.code=QUOTA_STORE_DOWN".to_string()));
+        assert!(events[2]
+            .1
+            .contains(&"error.code=EXPORT_FAILED".to_string()));
+    }
+
+    #[derive(Debug, Error)]
+    #[error("connection reset")]
+    pub struct ResetError;
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum UploadError {
+        #[error("Upload rejected")]
+        #[response(status_code = 413, code = "UPLOAD_REJECTED")]
+        Rejected,
+        #[error("Upload failed")]
+        #[response(status_code = 502, code = "UPLOAD_FAILED")]
+        Failed(#[source] ResetError),
+    }
+
+    #[test]
+    fn logs_every_variant_by_status() {
+        let events = Events::default();
+        let subscriber = tracing_subscriber::registry().with(events.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _ = UploadError::Rejected.into_response();
+            let _ = UploadError::Failed(ResetError).into_response();
+        });
+
+        let events = events.0.lock().unwrap();
+        assert_eq!(events[0].0, Level::WARN);
+        assert_eq!(events[1].0, Level::ERROR);
+        assert!(events[1]
+            .1
+            .contains(&r#"error.sources=["connection reset"]"#.to_string()));
+    }
+}
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use axum_error_handler::{AxumErrorResponse, EnvelopeConfig};
+    use thiserror::Error;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Error, AxumErrorResponse)]
+    #[response(namespace = "USER")]
+    pub enum UserError {
+        #[error("User not found")]
+        #[response(status_code = 404, code = "NOT_FOUND")]
+        NotFound,
+        #[error("User is banned")]
+        #[response(status_code = 403, code = "BANNED")]
+        Banned,
+    }
+
+    #[test]
+    fn emits_code_union_and_body_interfaces() {
+        axum_error_handler::set_envelope_config(
+            EnvelopeConfig::new()
+                .result_member("data")
+                .error_member("error-info"),
+        )
+        .unwrap();
+
+        let definitions = axum_error_handler::typescript_definitions();
+
+        assert!(definitions.contains("export type ErrorCode =\n"));
+        assert!(definitions.contains("\n  | \"USER.BANNED\""));
+        assert!(definitions.contains("\n  | \"USER.NOT_FOUND\""));
+        assert!(definitions.contains("\n  | \"INTERNAL_SERVER_ERROR\""));
+        #[cfg(feature = "axum")]
+        assert!(definitions.contains("\n  | \"ROUTE_NOT_FOUND\""));
+        assert!(definitions.contains(
+            "export interface ErrorBody {\n  code: ErrorCode;\n  message: string;\n  help_url?: string;\n"
+        ));
+        assert!(definitions.contains(
+            "export interface ErrorEnvelope {\n  data: null;\n  \"error-info\": ErrorBody;\n}\n"
+        ));
+        assert!(definitions.contains("  code: ErrorCode;\n  help_url?: string;"));
+        assert!(definitions.contains("  [member: string]: unknown;\n}"));
+    }
+}
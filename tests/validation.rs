@@ -0,0 +1,103 @@
+#[cfg(test)]
+#[cfg(feature = "validator")]
+mod tests {
+    use axum::{
+        body::to_bytes,
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    };
+    use axum_error_handler::{
+        AxumErrorResponse, FieldError, IntoErrorResponseContext, ValidationRejection,
+    };
+    use serde_json::{json, Value};
+    use thiserror::Error;
+    use validator::{Validate, ValidationErrors};
+
+    #[derive(Debug, Validate)]
+    pub struct Address {
+        #[validate(length(min = 1))]
+        city: String,
+    }
+
+    #[derive(Debug, Validate)]
+    pub struct LineItem {
+        #[validate(range(min = 1, message = "must order at least one"))]
+        quantity: u32,
+    }
+
+    #[derive(Debug, Validate)]
+    pub struct NewOrder {
+        #[validate(email)]
+        email: String,
+        #[validate(nested)]
+        address: Address,
+        #[validate(nested)]
+        items: Vec<LineItem>,
+    }
+
+    #[derive(Debug, Error, AxumErrorResponse)]
+    pub enum OrderError {
+        #[error(transparent)]
+        #[response(nested)]
+        Invalid(#[from] ValidationErrors),
+    }
+
+    fn invalid_order() -> NewOrder {
+        NewOrder {
+            email: "nope".to_string(),
+            address: Address {
+                city: String::new(),
+            },
+            items: vec![LineItem { quantity: 2 }, LineItem { quantity: 0 }],
+        }
+    }
+
+    async fn body_json(resp: Response) -> Value {
+        let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn flattens_field_errors() {
+        let ctx = invalid_order()
+            .validate()
+            .unwrap_err()
+            .into_response_context();
+        assert_eq!(ctx.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(ctx.code(), "VALIDATION_ERROR");
+        assert_eq!(
+            ctx.field_errors(),
+            [
+                FieldError::new("address.city", "LENGTH", "failed the length validation"),
+                FieldError::new("email", "EMAIL", "failed the email validation"),
+                FieldError::new("items[1].quantity", "RANGE", "must order at least one"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_the_wrapper() {
+        let handler = || -> Result<(), ValidationRejection> {
+            invalid_order().validate()?;
+            Ok(())
+        };
+
+        let resp = handler().unwrap_err().into_response();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = body_json(resp).await;
+        assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+        assert_eq!(
+            body["error"]["fields"][1],
+            json!({"field": "email", "code": "EMAIL", "message": "failed the email validation"})
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_nested_validation_errors() {
+        let err = OrderError::from(invalid_order().validate().unwrap_err());
+        let body = body_json(err.into_response()).await;
+        assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+        assert_eq!(body["error"]["fields"].as_array().unwrap().len(), 3);
+    }
+}